@@ -1,19 +1,32 @@
 use utils::to_i4;
 use wasm_bindgen::prelude::*;
 
-use crate::utils;
+use crate::{
+    chars, fixed::QFormat, float::FloatValue, grouping::FormatOptions, literal::Literal, memory,
+    utils,
+};
 
 #[wasm_bindgen]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Results")
+)]
 pub struct Results {
     flags: ResultFlags,
     values: ResultValue,
+    field: Option<String>,
 }
 
 #[wasm_bindgen]
 impl Results {
     pub fn new(flags: ResultFlags, values: ResultValue) -> Self {
-        Self { flags, values }
+        Self {
+            flags,
+            values,
+            field: None,
+        }
     }
 
     #[wasm_bindgen(getter)]
@@ -25,10 +38,200 @@ impl Results {
     pub fn get_value(&self) -> ResultValue {
         self.values.clone()
     }
+
+    /// the binary result with the touched bit-field wrapped in brackets,
+    /// e.g. `00[1101]00`, only set by the bit-field operations.
+    #[wasm_bindgen(getter)]
+    pub fn get_field(&self) -> Option<String> {
+        self.field.clone()
+    }
+}
+
+impl Results {
+    /// builds the results of an operation which only touches the zero and
+    /// negative flags, `None` if the width is not supported.
+    pub fn from_raw(raw: u32, of: i32) -> Option<Self> {
+        let values = ResultValue::from_raw(raw, of)?;
+        let raw = values.get_raw();
+        let zero = raw == 0;
+        let negative = raw >> (of - 1) & 1 == 1;
+        let flags = ResultFlags::new(zero, negative, false, false);
+
+        Some(Self::new(flags, values))
+    }
+
+    pub(crate) fn with_values(mut self, values: ResultValue) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// marks the bits `lsb..lsb + width` as the field of interest.
+    pub fn with_field(mut self, lsb: u32, width: u32) -> Self {
+        let bin = &self.values.bin;
+        let len = bin.len();
+        let start = len - (lsb + width) as usize;
+        let end = len - lsb as usize;
+        self.field = Some(format!(
+            "{}[{}]{}",
+            &bin[..start],
+            &bin[start..end],
+            &bin[end..]
+        ));
+        self
+    }
+}
+
+/// An operation to run, with the `serde` feature it reads from objects like
+/// `{"op": "add", "left": 5, "right": 3, "width": 8}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Request {
+    pub op: String,
+    pub left: i32,
+    pub right: i32,
+    pub width: i32,
+}
+
+impl Request {
+    pub fn run(&self) -> Result<Results, String> {
+        crate::operation(&self.op, self.left, self.right, self.width)
+    }
+}
+
+/// Result of a packed lane operation (`SADD8`, `UADD16`, ...), the 32-bit
+/// register is split into lanes, each lane keeps its own results.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Lane")
+)]
+pub struct LaneResults {
+    lanes: Vec<Results>,
+    values: ResultValue,
+    /// the GE\[3:0\] bits as set by the Cortex-M4, one bit per byte.
+    pub ge: u8,
+}
+
+#[wasm_bindgen]
+impl LaneResults {
+    pub fn new(lanes: Vec<Results>, values: ResultValue, ge: u8) -> Self {
+        Self { lanes, values, ge }
+    }
+
+    /// the results of every lane, starting at the least significant one.
+    #[wasm_bindgen(getter)]
+    pub fn get_lanes(&self) -> Vec<Results> {
+        self.lanes.clone()
+    }
+
+    /// the packed 32-bit register value.
+    #[wasm_bindgen(getter)]
+    pub fn get_value(&self) -> ResultValue {
+        self.values.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_ge_bin(&self) -> String {
+        format!("{:04b}", self.ge)
+    }
+}
+
+/// Result of a packed BCD addition, the binary sum before the decimal
+/// adjust and the corrected decimal sum.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Bcd")
+)]
+pub struct BcdResults {
+    binary: Results,
+    decimal: Results,
+    /// the value added to the binary sum by the decimal adjust, like `0x66`.
+    pub correction: u32,
+    /// the carry from bit 3 into bit 4 of the binary sum (AF on x86).
+    pub half_carry: bool,
+    /// the decimal carry out of the most significant digit.
+    pub carry: bool,
+}
+
+#[wasm_bindgen]
+impl BcdResults {
+    pub fn new(binary: Results, decimal: Results, correction: u32, half_carry: bool) -> Self {
+        let carry = decimal.flags.carry;
+        Self {
+            binary,
+            decimal,
+            correction,
+            half_carry,
+            carry,
+        }
+    }
+
+    /// the plain binary sum, before the decimal adjust.
+    #[wasm_bindgen(getter)]
+    pub fn get_binary(&self) -> Results {
+        self.binary.clone()
+    }
+
+    /// the decimal adjusted sum, the carry flag is the decimal carry.
+    #[wasm_bindgen(getter)]
+    pub fn get_decimal(&self) -> Results {
+        self.decimal.clone()
+    }
+}
+
+/// Result of a fixed-point operation, the results of the raw pattern and
+/// its real value.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Fixed")
+)]
+pub struct FixedResults {
+    results: Results,
+    real: String,
+    /// the real result does not fit into the format.
+    pub overflow: bool,
+    /// bits were lost by the rounding of a product.
+    pub precision_loss: bool,
+}
+
+#[wasm_bindgen]
+impl FixedResults {
+    pub fn new(results: Results, real: String, overflow: bool, precision_loss: bool) -> Self {
+        Self {
+            results,
+            real,
+            overflow,
+            precision_loss,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+
+    /// the exact real value of the result pattern.
+    #[wasm_bindgen(getter)]
+    pub fn get_real(&self) -> String {
+        self.real.clone()
+    }
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Flags")
+)]
 pub struct ResultFlags {
     pub zero: bool,
     pub negative: bool,
@@ -49,14 +252,76 @@ impl ResultFlags {
     }
 }
 
+/// The condition codes shown for unsigned comparisons, with their meaning.
+pub const UNSIGNED_CONDITIONS: [(&str, &str); 6] = [
+    ("EQ", "Equal"),
+    ("NE", "Not equal"),
+    ("HS", "Higher or same"),
+    ("LO", "Lower"),
+    ("HI", "Higher"),
+    ("LS", "Lower or same"),
+];
+
+/// The condition codes shown for signed comparisons, with their meaning.
+pub const SIGNED_CONDITIONS: [(&str, &str); 10] = [
+    ("EQ", "Equal"),
+    ("NE", "Not equal"),
+    ("MI", "Minus / Negative"),
+    ("PL", "Plus / Positive or zero"),
+    ("VS", "Overflow"),
+    ("VC", "No overflow"),
+    ("GE", "Greater or equal"),
+    ("LT", "Less than"),
+    ("GT", "Greater than"),
+    ("LE", "Less or equal"),
+];
+
+#[wasm_bindgen]
+impl ResultFlags {
+    /// whether the condition code like `EQ`, `HS` or `GT` holds after the
+    /// operation, `None` for unknown codes. `CS` and `CC` are `HS` and `LO`.
+    pub fn condition(&self, code: &str) -> Option<bool> {
+        let (z, n, v, c) = (self.zero, self.negative, self.overflow, self.carry);
+        let holds = match code.to_uppercase().as_str() {
+            "EQ" => z,
+            "NE" => !z,
+            "HS" | "CS" => c,
+            "LO" | "CC" => !c,
+            "MI" => n,
+            "PL" => !n,
+            "VS" => v,
+            "VC" => !v,
+            "HI" => c && !z,
+            "LS" => !c || z,
+            "GE" => n == v,
+            "LT" => n != v,
+            "GT" => !z && n == v,
+            "LE" => z || n != v,
+            "AL" => true,
+            _ => return None,
+        };
+        Some(holds)
+    }
+}
+
 #[wasm_bindgen]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Formatted")
+)]
 pub struct FormattedValue {
+    raw: u32,
     signed: String,
     unsigned: String,
-    bin: String,
+    pub(crate) bin: String,
     com: String,
-    hex: String,
+    pub(crate) hex: String,
+    oct: String,
+    /// how [`Self::get_bin`] and [`Self::get_hex`] write the digits.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) options: FormatOptions,
 }
 
 #[wasm_bindgen]
@@ -73,7 +338,7 @@ impl FormattedValue {
 
     #[wasm_bindgen(getter)]
     pub fn get_bin(&self) -> String {
-        self.bin.clone()
+        self.options.bin(&self.bin)
     }
 
     #[wasm_bindgen(getter)]
@@ -83,52 +348,506 @@ impl FormattedValue {
 
     #[wasm_bindgen(getter)]
     pub fn get_hex(&self) -> String {
-        self.hex.clone()
+        self.options.hex(&self.hex)
     }
-}
 
-#[wasm_bindgen]
-pub fn format(value: i32, of: i32) -> Result<FormattedValue, JsValue> {
-    match of {
-        4 => {
-            let value = value as u8 & 0xF;
-            let com = !value + 1;
-            let svalue = to_i4(value);
-            Ok(FormattedValue::new4(value, svalue, com))
+    #[wasm_bindgen(getter)]
+    pub fn get_oct(&self) -> String {
+        self.oct.clone()
+    }
+
+    /// the bit pattern, zero extended to 32 bit.
+    #[wasm_bindgen(getter)]
+    pub fn get_raw(&self) -> u32 {
+        self.raw
+    }
+
+    /// the signed value in sign-magnitude, `None` if it can not be
+    /// represented (the most negative value).
+    #[wasm_bindgen(getter)]
+    pub fn get_sign_magnitude(&self) -> Option<String> {
+        formatter::sign_magnitude(self.raw, self.bin.len())
+    }
+
+    /// the signed value in ones' complement, `None` if it can not be
+    /// represented (the most negative value).
+    #[wasm_bindgen(getter)]
+    pub fn get_ones(&self) -> Option<String> {
+        formatter::ones(self.raw, self.bin.len())
+    }
+
+    /// the signed value in excess-K with `K = 2^(of - 1)`.
+    #[wasm_bindgen(getter)]
+    pub fn get_excess(&self) -> Option<String> {
+        self.excess(1i64 << (self.bin.len() - 1))
+    }
+
+    /// the signed value in excess-K with the given bias, `None` if the
+    /// biased value does not fit into the width.
+    pub fn excess(&self, bias: i64) -> Option<String> {
+        formatter::excess(self.raw, self.bin.len(), bias)
+    }
+
+    /// the Latin-1 character of every byte, most significant byte first,
+    /// `None` for 4 bits.
+    #[wasm_bindgen(getter)]
+    pub fn get_chars(&self) -> Option<Vec<String>> {
+        let of = self.bin.len();
+        if of < 8 {
+            return None;
         }
-        8 => {
-            let value = value as u8;
-            let com = !value + 1;
-            let svalue = value as i8;
-            Ok(FormattedValue::new(value, svalue, com))
+        let bytes = self.raw.to_be_bytes();
+        Some(
+            bytes[4 - of / 8..]
+                .iter()
+                .map(|b| chars::character(*b))
+                .collect(),
+        )
+    }
+
+    /// the bytes read as UTF-8 text, most significant byte first, leading
+    /// zero bytes are skipped. `None` for 4 bits and invalid UTF-8.
+    #[wasm_bindgen(getter)]
+    pub fn get_utf8(&self) -> Option<String> {
+        let of = self.bin.len();
+        if of < 8 {
+            return None;
         }
-        16 => {
-            let value = value as u16;
-            let com = !value + 1;
-            let svalue = value as i16;
-            Ok(FormattedValue::new(value, svalue, com))
+        let bytes = self.raw.to_be_bytes();
+        let bytes = &bytes[4 - of / 8..];
+        let start = bytes
+            .iter()
+            .position(|b| *b != 0)
+            .unwrap_or(bytes.len() - 1);
+        std::str::from_utf8(&bytes[start..]).ok().map(String::from)
+    }
+
+    /// the bytes in little-endian memory order, `None` below 16 bits.
+    #[wasm_bindgen(getter)]
+    pub fn get_le_bytes(&self) -> Option<Vec<u8>> {
+        memory::bytes(self.raw, self.bin.len(), true)
+    }
+
+    /// the bytes in big-endian memory order, `None` below 16 bits.
+    #[wasm_bindgen(getter)]
+    pub fn get_be_bytes(&self) -> Option<Vec<u8>> {
+        memory::bytes(self.raw, self.bin.len(), false)
+    }
+
+    /// the little-endian bytes at their addresses, like `+0: 78  +1: 56`.
+    #[wasm_bindgen(getter)]
+    pub fn get_little_endian(&self) -> Option<String> {
+        self.get_le_bytes().map(|bytes| memory::layout(&bytes))
+    }
+
+    /// the big-endian bytes at their addresses, like `+0: 12  +1: 34`.
+    #[wasm_bindgen(getter)]
+    pub fn get_big_endian(&self) -> Option<String> {
+        self.get_be_bytes().map(|bytes| memory::layout(&bytes))
+    }
+
+    /// the real value of the pattern in the Q format `format`, like `Q4.4`.
+    pub fn fixed(&self, format: &str) -> Result<String, JsValue> {
+        let q = QFormat::parse(format, self.bin.len() as i32)?;
+        Ok(q.real(self.raw))
+    }
+
+    /// the pattern as IEEE 754 half or single precision float, `None` for
+    /// the other widths.
+    #[wasm_bindgen(getter)]
+    pub fn get_float(&self) -> Option<FloatValue> {
+        FloatValue::of(self.raw as u64, self.bin.len() as i32)
+    }
+
+    /// the value as Verilog literal, like `8'hFF`.
+    #[wasm_bindgen(getter)]
+    pub fn get_verilog(&self) -> String {
+        hdl::verilog(&self.bin, &self.hex)
+    }
+
+    /// the value as binary Verilog literal, like `4'b1011`.
+    #[wasm_bindgen(getter)]
+    pub fn get_verilog_bin(&self) -> String {
+        hdl::verilog_bin(&self.bin)
+    }
+
+    /// the value as VHDL literal, like `x"FF"`.
+    #[wasm_bindgen(getter)]
+    pub fn get_vhdl(&self) -> String {
+        hdl::vhdl(&self.hex)
+    }
+
+    /// the value as binary VHDL literal, like `"1011"`.
+    #[wasm_bindgen(getter)]
+    pub fn get_vhdl_bin(&self) -> String {
+        hdl::vhdl_bin(&self.bin)
+    }
+}
+
+/// What [`format`] reads, a number like the operations take or a literal in
+/// any notation [`Literal::parse`] knows, like `0x7F`, `4'b1011`, `8'shF3`
+/// or `x"3F"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatInput<'a> {
+    Number(i32),
+    Literal(&'a str),
+}
+
+impl From<i32> for FormatInput<'_> {
+    fn from(value: i32) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl<'a> From<&'a str> for FormatInput<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::Literal(value)
+    }
+}
+
+/// Formats the lower `of` bits of a number, or a literal. The size written
+/// in a sized literal is checked against `of`, a smaller one is zero or sign
+/// extended like in Verilog.
+pub fn format<'a>(value: impl Into<FormatInput<'a>>, of: i32) -> Result<FormattedValue, JsValue> {
+    match value.into() {
+        FormatInput::Number(value) => {
+            FormattedValue::of(value, of).ok_or_else(|| JsValue::from("unsupported value"))
         }
-        32 => {
-            let value = value as u32;
-            let com = !value + 1;
-            let svalue = value as i32;
-            Ok(FormattedValue::new(value, svalue, com))
+        FormatInput::Literal(input) => Ok(Literal::parse(input, 10, of)?.get_formatted()),
+    }
+}
+
+/// [`format`] for JavaScript, `value` is a number or a string. The binary
+/// and hex digits are written with `options` if given, see
+/// [`FormattedValue::with_options`].
+#[wasm_bindgen(js_name = format)]
+pub fn format_js(
+    value: JsValue,
+    of: i32,
+    options: Option<FormatOptions>,
+) -> Result<FormattedValue, JsValue> {
+    let formatted = if let Some(input) = value.as_string() {
+        format(input.as_str(), of)
+    } else if let Some(number) = value.as_f64() {
+        // like the `i32` arguments of the other exports
+        format(number as i64 as i32, of)
+    } else {
+        Err(JsValue::from("a value is a number or a literal"))
+    }?;
+    Ok(formatted.with_options(&options.unwrap_or_default()))
+}
+
+impl FormattedValue {
+    /// formats the lower `of` bits of `value`, `None` if the width is not
+    /// supported.
+    pub fn of(value: i32, of: i32) -> Option<Self> {
+        match of {
+            4 => {
+                let value = value as u8 & 0xF;
+                let com = (!value).wrapping_add(1);
+                let svalue = to_i4(value);
+                Some(FormattedValue::new4(value, svalue, com))
+            }
+            8 => {
+                let value = value as u8;
+                let com = (!value).wrapping_add(1);
+                let svalue = value as i8;
+                Some(FormattedValue::new(value, svalue, com))
+            }
+            16 => {
+                let value = value as u16;
+                let com = (!value).wrapping_add(1);
+                let svalue = value as i16;
+                Some(FormattedValue::new(value, svalue, com))
+            }
+            32 => {
+                let value = value as u32;
+                let com = (!value).wrapping_add(1);
+                let svalue = value as i32;
+                Some(FormattedValue::new(value, svalue, com))
+            }
+            _ => None,
         }
-        _ => Err(JsValue::from("unsupported value")),
     }
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Value")
+)]
 pub struct ResultValue {
+    raw: u32,
     signed: String,
     unsigned: String,
-    bin: String,
-    hex: String,
+    pub(crate) bin: String,
+    pub(crate) hex: String,
+    /// how [`Self::get_bin`] and [`Self::get_hex`] write the digits.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) options: FormatOptions,
+}
+
+/// The plain shapes of the result types, a deserialized value is only
+/// accepted if its display strings and flags agree with its bits.
+#[cfg(feature = "serde")]
+mod checked {
+    use serde::Deserialize;
+
+    use crate::{fixed::QFormat, grouping::FormatOptions};
+
+    #[derive(Deserialize)]
+    pub struct Results {
+        flags: super::ResultFlags,
+        values: super::ResultValue,
+        field: Option<String>,
+    }
+
+    impl TryFrom<Results> for super::Results {
+        type Error = String;
+
+        fn try_from(results: Results) -> Result<Self, String> {
+            if let Some(field) = &results.field {
+                let open = field.find('[');
+                let close = field.find(']');
+                let marked = open < close
+                    && field.matches('[').count() == 1
+                    && field.matches(']').count() == 1;
+                if !marked || field.replace(['[', ']'], "") != results.values.bin {
+                    return Err(format!("the field {} does not mark the result", field));
+                }
+            }
+            Ok(Self {
+                flags: results.flags,
+                values: results.values,
+                field: results.field,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Lane {
+        lanes: Vec<super::Results>,
+        values: super::ResultValue,
+        ge: u8,
+    }
+
+    impl TryFrom<Lane> for super::LaneResults {
+        type Error = String;
+
+        fn try_from(lane: Lane) -> Result<Self, String> {
+            let count = lane.lanes.len();
+            if !matches!(count, 2 | 4) || lane.values.bin.len() != 32 {
+                return Err("a register has 2 or 4 lanes of 32 bits".to_string());
+            }
+            let size = 32 / count;
+            let ge_per_lane = size / 8;
+            let ge_mask = (1u8 << ge_per_lane) - 1;
+
+            let mut packed = 0u32;
+            // the GE bits of the signed and of the unsigned operations
+            let (mut signed, mut unsigned) = (0u8, 0u8);
+            for (i, res) in lane.lanes.iter().enumerate() {
+                if res.values.bin.len() != size {
+                    return Err(format!("lane {} does not have {} bits", i, size));
+                }
+                packed |= res.values.raw << (i * size);
+                let flags = &res.flags;
+                if flags.negative == flags.overflow {
+                    signed |= ge_mask << (i * ge_per_lane);
+                }
+                if flags.carry {
+                    unsigned |= ge_mask << (i * ge_per_lane);
+                }
+            }
+            if packed != lane.values.raw {
+                return Err(format!("the lanes do not pack to {:#X}", lane.values.raw));
+            }
+            if lane.ge != signed && lane.ge != unsigned {
+                return Err(format!(
+                    "the GE bits {:04b} do not match the lanes",
+                    lane.ge
+                ));
+            }
+            Ok(Self::new(lane.lanes, lane.values, lane.ge))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Bcd {
+        binary: super::Results,
+        decimal: super::Results,
+        correction: u32,
+        half_carry: bool,
+        carry: bool,
+    }
+
+    impl TryFrom<Bcd> for super::BcdResults {
+        type Error = String;
+
+        fn try_from(bcd: Bcd) -> Result<Self, String> {
+            let of = bcd.binary.values.bin.len();
+            if bcd.decimal.values.bin.len() != of {
+                return Err("both sums have the same width".to_string());
+            }
+            if bcd.carry != bcd.decimal.flags.carry {
+                return Err("the carry is the one of the decimal sum".to_string());
+            }
+            let decimal = bcd.decimal.values.raw;
+            if (0..of / 4).any(|i| decimal >> (i * 4) & 0xF > 9) {
+                return Err(format!("{:#X} is no packed BCD value", decimal));
+            }
+            // the binary sum plus the correction is the decimal sum
+            let plain = bcd.binary.values.raw as u64 | (bcd.binary.flags.carry as u64) << of;
+            let adjusted = decimal as u64 | (bcd.carry as u64) << of;
+            if plain + bcd.correction as u64 != adjusted {
+                return Err(format!(
+                    "the correction {:#X} does not adjust the binary sum",
+                    bcd.correction
+                ));
+            }
+            Ok(Self::new(
+                bcd.binary,
+                bcd.decimal,
+                bcd.correction,
+                bcd.half_carry,
+            ))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Fixed {
+        results: super::Results,
+        real: String,
+        overflow: bool,
+        precision_loss: bool,
+    }
+
+    impl TryFrom<Fixed> for super::FixedResults {
+        type Error = String;
+
+        fn try_from(fixed: Fixed) -> Result<Self, String> {
+            // the format is not part of the results, the real value has to
+            // be the one of the pattern in any Q format of its width
+            let bits = fixed.results.values.bin.len() as u32;
+            let raw = fixed.results.values.raw;
+            let matches = (0..=bits).any(|frac| {
+                [true, false].iter().any(|&signed| {
+                    let q = QFormat {
+                        signed,
+                        int: bits - frac,
+                        frac,
+                    };
+                    (!signed || q.int > 0) && q.real(raw) == fixed.real
+                })
+            });
+            if !matches {
+                return Err(format!("{} is not the value of {:#X}", fixed.real, raw));
+            }
+            Ok(Self::new(
+                fixed.results,
+                fixed.real,
+                fixed.overflow,
+                fixed.precision_loss,
+            ))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Flags {
+        zero: bool,
+        negative: bool,
+        overflow: bool,
+        carry: bool,
+        borrow: bool,
+    }
+
+    impl TryFrom<Flags> for super::ResultFlags {
+        type Error = String;
+
+        fn try_from(flags: Flags) -> Result<Self, String> {
+            if flags.borrow == flags.carry {
+                return Err("the borrow is the inverted carry".to_string());
+            }
+            Ok(Self::new(
+                flags.zero,
+                flags.negative,
+                flags.overflow,
+                flags.carry,
+            ))
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Value {
+        raw: u32,
+        signed: String,
+        unsigned: String,
+        bin: String,
+        hex: String,
+    }
+
+    impl TryFrom<Value> for super::ResultValue {
+        type Error = String;
+
+        fn try_from(value: Value) -> Result<Self, String> {
+            let value = Self {
+                raw: value.raw,
+                signed: value.signed,
+                unsigned: value.unsigned,
+                bin: value.bin,
+                hex: value.hex,
+                options: FormatOptions::default(),
+            };
+            match Self::from_raw(value.raw, value.bin.len() as i32) {
+                Some(expected) if expected == value => Ok(value),
+                _ => Err(format!(
+                    "the value does not match its bits {:#X}",
+                    value.raw
+                )),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Formatted {
+        raw: u32,
+        signed: String,
+        unsigned: String,
+        bin: String,
+        com: String,
+        hex: String,
+        oct: String,
+    }
+
+    impl TryFrom<Formatted> for super::FormattedValue {
+        type Error = String;
+
+        fn try_from(value: Formatted) -> Result<Self, String> {
+            let value = Self {
+                raw: value.raw,
+                signed: value.signed,
+                unsigned: value.unsigned,
+                bin: value.bin,
+                com: value.com,
+                hex: value.hex,
+                oct: value.oct,
+                options: FormatOptions::default(),
+            };
+            match Self::of(value.raw as i32, value.bin.len() as i32) {
+                Some(expected) if expected == value => Ok(value),
+                _ => Err(format!(
+                    "the value does not match its bits {:#X}",
+                    value.raw
+                )),
+            }
+        }
+    }
 }
 
 mod formatter {
-    use crate::api::FormattedValue;
+    use crate::{api::FormattedValue, grouping::FormatOptions, utils};
 
     use super::ResultValue;
     use std::fmt::{Binary, Display, UpperHex};
@@ -138,35 +857,89 @@ mod formatter {
             let res = ResultValue::new4(unsigned, signed);
             let s = format!("{:b}", complement);
             let mut comp = fix_size::<u8>(s, 8);
-            comp = (&comp[comp.len() - 4..]).to_string();
+            comp = comp[comp.len() - 4..].to_string();
 
             Self {
+                raw: res.raw,
                 signed: res.signed,
                 unsigned: res.unsigned,
                 bin: res.bin,
                 hex: res.hex,
                 com: comp,
+                oct: format!("{:02o}", unsigned),
+                options: res.options,
             }
         }
 
         pub fn new<U, S>(unsigned: U, signed: S, complement: U) -> Self
         where
-            U: num::Unsigned + Display + UpperHex + Binary,
+            U: num::Unsigned + Display + UpperHex + Binary + Copy + Into<u32>,
             S: num::Signed + Display,
         {
             let res = ResultValue::new(unsigned, signed);
             let s = format!("{:b}", complement);
             let comp = fix_size::<U>(s, 8);
+            let digits = (std::mem::size_of::<U>() * 8).div_ceil(3);
             Self {
+                raw: res.raw,
                 signed: res.signed,
                 unsigned: res.unsigned,
                 bin: res.bin,
                 hex: res.hex,
                 com: comp,
+                oct: format!("{:0digits$o}", res.raw, digits = digits),
+                options: res.options,
             }
         }
     }
 
+    /// the signed value of the lower `bits` of `raw`.
+    fn signed(raw: u32, bits: usize) -> i64 {
+        let raw = raw as i64;
+        if raw >> (bits - 1) & 1 == 1 {
+            raw - (1 << bits)
+        } else {
+            raw
+        }
+    }
+
+    fn bin(value: u64, bits: usize) -> String {
+        format!("{:0bits$b}", value, bits = bits)
+    }
+
+    /// sign-magnitude and ones' complement can not hold the most negative
+    /// two's complement value, as they have two zeros.
+    fn symmetric(raw: u32, bits: usize) -> Option<i64> {
+        let value = signed(raw, bits);
+        Some(value).filter(|value| *value != -(1 << (bits - 1)))
+    }
+
+    pub fn sign_magnitude(raw: u32, bits: usize) -> Option<String> {
+        let value = symmetric(raw, bits)?;
+        let sign = if value < 0 { 1 << (bits - 1) } else { 0 };
+        Some(bin(sign | value.unsigned_abs(), bits))
+    }
+
+    pub fn ones(raw: u32, bits: usize) -> Option<String> {
+        let value = symmetric(raw, bits)?;
+        let mask = (1u64 << bits) - 1;
+        let pattern = if value < 0 {
+            !value.unsigned_abs() & mask
+        } else {
+            value as u64
+        };
+        Some(bin(pattern, bits))
+    }
+
+    pub fn excess(raw: u32, bits: usize, bias: i64) -> Option<String> {
+        let biased = signed(raw, bits).checked_add(bias)?;
+        if (0..1 << bits).contains(&biased) {
+            Some(bin(biased as u64, bits))
+        } else {
+            None
+        }
+    }
+
     fn fix_size<T>(s: String, mult: usize) -> String {
         let size = std::mem::size_of::<T>() * mult;
         format!("{}{}", "0".repeat(size - s.len()), s)
@@ -177,28 +950,71 @@ mod formatter {
     impl ResultValue {
         pub fn new<U, S>(unsigned: U, signed: S) -> Self
         where
-            U: num::Unsigned + Display + UpperHex + Binary,
+            U: num::Unsigned + Display + UpperHex + Binary + Copy + Into<u32>,
             S: num::Signed + Display,
         {
             Self {
+                raw: unsigned.into(),
                 unsigned: format!("{}", unsigned),
                 signed: format!("{}", signed),
                 hex: fix_size::<U>(format!("{:X}", unsigned), 2),
                 bin: fix_size::<U>(format!("{:b}", unsigned), 8),
+                options: FormatOptions::default(),
+            }
+        }
+
+        /// formats the lower `of` bits of `raw`, `None` if the width is
+        /// not supported.
+        pub fn from_raw(raw: u32, of: i32) -> Option<Self> {
+            match of {
+                4 => {
+                    let value = utils::to_u4(raw as u8);
+                    Some(Self::new4(value, utils::to_i4(value)))
+                }
+                8 => Some(Self::new(raw as u8, raw as u8 as i8)),
+                16 => Some(Self::new(raw as u16, raw as u16 as i16)),
+                32 => Some(Self::new(raw, raw as i32)),
+                _ => None,
             }
         }
 
         pub fn new4(unsigned: u8, signed: i8) -> Self {
             let mut res = Self::new(unsigned, signed);
-            res.hex = (&res.hex[res.hex.len() - 1..]).to_string();
-            res.bin = (&res.bin[res.bin.len() - 4..]).to_string();
+            res.hex = res.hex[res.hex.len() - 1..].to_string();
+            res.bin = res.bin[res.bin.len() - 4..].to_string();
             res
         }
     }
 }
 
+/// Hardware description language notations, the width is given by the
+/// amount of binary digits.
+mod hdl {
+    pub fn verilog(bin: &str, hex: &str) -> String {
+        format!("{}'h{}", bin.len(), hex)
+    }
+
+    pub fn verilog_bin(bin: &str) -> String {
+        format!("{}'b{}", bin.len(), bin)
+    }
+
+    pub fn vhdl(hex: &str) -> String {
+        format!("x\"{}\"", hex)
+    }
+
+    pub fn vhdl_bin(bin: &str) -> String {
+        format!("\"{}\"", bin)
+    }
+}
+
 #[wasm_bindgen]
 impl ResultValue {
+    /// the unsigned result as a plain number, zero extended to 32 bit.
+    #[wasm_bindgen(getter)]
+    pub fn get_raw(&self) -> u32 {
+        self.raw
+    }
+
     #[wasm_bindgen(getter)]
     pub fn get_signed(&self) -> String {
         self.signed.clone()
@@ -211,11 +1027,35 @@ impl ResultValue {
 
     #[wasm_bindgen(getter)]
     pub fn get_bin(&self) -> String {
-        self.bin.clone()
+        self.options.bin(&self.bin)
     }
 
     #[wasm_bindgen(getter)]
     pub fn get_hex(&self) -> String {
-        self.hex.clone()
+        self.options.hex(&self.hex)
+    }
+
+    /// the value as Verilog literal, like `8'hFF`.
+    #[wasm_bindgen(getter)]
+    pub fn get_verilog(&self) -> String {
+        hdl::verilog(&self.bin, &self.hex)
+    }
+
+    /// the value as binary Verilog literal, like `4'b1011`.
+    #[wasm_bindgen(getter)]
+    pub fn get_verilog_bin(&self) -> String {
+        hdl::verilog_bin(&self.bin)
+    }
+
+    /// the value as VHDL literal, like `x"FF"`.
+    #[wasm_bindgen(getter)]
+    pub fn get_vhdl(&self) -> String {
+        hdl::vhdl(&self.hex)
+    }
+
+    /// the value as binary VHDL literal, like `"1011"`.
+    #[wasm_bindgen(getter)]
+    pub fn get_vhdl_bin(&self) -> String {
+        hdl::vhdl_bin(&self.bin)
     }
 }
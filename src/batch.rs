@@ -0,0 +1,128 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    addition::add_raw,
+    api::ResultFlags,
+    logical::{run_table, table_of},
+    OPERATIONS,
+};
+
+/// The bits of a packed flag mask, in the order of the APSR: `N Z C V`.
+pub const FLAG_NEGATIVE: u8 = 0b1000;
+pub const FLAG_ZERO: u8 = 0b0100;
+pub const FLAG_CARRY: u8 = 0b0010;
+pub const FLAG_OVERFLOW: u8 = 0b0001;
+
+/// The raw results and packed flags of many operations, element `i` belongs
+/// to the operands at `i`. The borrow is the inverted carry.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResults {
+    values: Vec<u32>,
+    flags: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl BatchResults {
+    /// the results as `Uint32Array`, zero extended to 32 bit.
+    #[wasm_bindgen(getter)]
+    pub fn get_values(&self) -> Vec<u32> {
+        self.values.clone()
+    }
+
+    /// the flags as `Uint8Array`, see [`FLAG_NEGATIVE`] and friends.
+    #[wasm_bindgen(getter)]
+    pub fn get_flags(&self) -> Vec<u8> {
+        self.flags.clone()
+    }
+}
+
+fn pack(flags: &ResultFlags) -> u8 {
+    let mut mask = 0;
+    if flags.negative {
+        mask |= FLAG_NEGATIVE;
+    }
+    if flags.zero {
+        mask |= FLAG_ZERO;
+    }
+    if flags.carry {
+        mask |= FLAG_CARRY;
+    }
+    if flags.overflow {
+        mask |= FLAG_OVERFLOW;
+    }
+    mask
+}
+
+impl BatchResults {
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    pub fn flags(&self) -> &[u8] {
+        &self.flags
+    }
+
+    /// Runs `op` on every pair of operands, like [`crate::operation`] but
+    /// without formatting a single value. It knows the same names, `sub`
+    /// sets the carry like `SUBS`, it is clear on a borrow.
+    pub fn run(op: &str, left: &[u32], right: &[u32], of: i32) -> Result<Self, String> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return Err("unsupported value".to_string());
+        }
+        if left.len() != right.len() {
+            return Err(format!(
+                "{} left but {} right operands",
+                left.len(),
+                right.len()
+            ));
+        }
+
+        let mask = if of == 32 { u32::MAX } else { (1 << of) - 1 };
+        let sign = 1 << (of - 1);
+
+        let run: Box<dyn Fn(u32, u32) -> (u32, u8)> = match op {
+            "add" => Box::new(|l, r| {
+                let (raw, flags) = add_raw(l, r, false, of);
+                (raw, pack(&flags))
+            }),
+            "sub" => Box::new(|l, r| {
+                let (raw, flags) = add_raw(l, !r, true, of);
+                (raw, pack(&flags))
+            }),
+            _ => {
+                let table = table_of(op)
+                    .filter(|_| OPERATIONS.contains(&op))
+                    .ok_or_else(|| format!("unknown operation '{}'", op))?;
+                Box::new(move |l, r| {
+                    let raw = run_table(table, l, r) & mask;
+                    let zero = if raw == 0 { FLAG_ZERO } else { 0 };
+                    let negative = if raw & sign != 0 { FLAG_NEGATIVE } else { 0 };
+                    (raw, zero | negative)
+                })
+            }
+        };
+
+        let (values, flags) = left.iter().zip(right).map(|(l, r)| run(*l, *r)).unzip();
+        Ok(Self { values, flags })
+    }
+}
+
+/// Runs `op` on every pair of an `Int32Array`, see [`BatchResults::run`].
+#[wasm_bindgen]
+pub fn batch(op: &str, left: &[i32], right: &[i32], of: i32) -> Result<BatchResults, JsValue> {
+    let left: Vec<u32> = left.iter().map(|l| *l as u32).collect();
+    let right: Vec<u32> = right.iter().map(|r| *r as u32).collect();
+    BatchResults::run(op, &left, &right, of).map_err(JsValue::from)
+}
+
+/// Runs `op` on every pair of a `Uint32Array`, see [`BatchResults::run`].
+#[wasm_bindgen]
+pub fn batch_unsigned(
+    op: &str,
+    left: &[u32],
+    right: &[u32],
+    of: i32,
+) -> Result<BatchResults, JsValue> {
+    BatchResults::run(op, left, right, of).map_err(JsValue::from)
+}
@@ -0,0 +1,105 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    addition::Add,
+    api::{BcdResults, ResultFlags, ResultValue, Results},
+    dispatch, utils,
+};
+
+fn digits(of: i32) -> Result<u32, JsValue> {
+    match of {
+        4 | 8 | 16 | 32 => Ok(of as u32 / 4),
+        _ => Err(JsValue::from("unsupported value")),
+    }
+}
+
+/// the decimal digits of a packed BCD value, starting at the least
+/// significant one.
+fn unpack(value: u32, digits: u32) -> Result<Vec<u8>, JsValue> {
+    (0..digits)
+        .map(|i| utils::nibble(value, i))
+        .map(|digit| {
+            if digit > 9 {
+                Err(JsValue::from(format!("invalid BCD digit {:X}", digit)))
+            } else {
+                Ok(digit)
+            }
+        })
+        .collect()
+}
+
+/// Encodes the non-negative `value` as packed BCD, one decimal digit per
+/// nibble.
+#[wasm_bindgen]
+pub fn bcd_encode(value: i32, of: i32) -> Result<Results, JsValue> {
+    let digits = digits(of)?;
+    if value < 0 {
+        return Err(JsValue::from("BCD has no negative values"));
+    }
+    if value as u64 >= 10u64.pow(digits) {
+        return Err(JsValue::from("the value has too many digits"));
+    }
+
+    let mut packed = 0;
+    let mut rest = value as u32;
+    for i in 0..digits {
+        packed |= (rest % 10) << (i * 4);
+        rest /= 10;
+    }
+
+    Ok(Results::from_raw(packed, of).expect("the width was checked before"))
+}
+
+/// Decodes the packed BCD `value` into its binary value.
+#[wasm_bindgen]
+pub fn bcd_decode(value: i32, of: i32) -> Result<Results, JsValue> {
+    let digits = unpack(value as u32, digits(of)?)?;
+    let decoded = digits
+        .iter()
+        .rev()
+        .fold(0u32, |acc, digit| acc * 10 + *digit as u32);
+
+    Ok(Results::from_raw(decoded, of).expect("the width was checked before"))
+}
+
+/// Adds two packed BCD values and does the decimal adjust, like DAA on x86
+/// or ADC in the decimal mode of the 6502.
+///
+/// Every digit whose binary sum is above 9 (or carried out) is corrected by
+/// adding 6, the correction is reported next to both sums.
+#[wasm_bindgen]
+pub fn bcd_add(left: i32, right: i32, of: i32) -> Result<BcdResults, JsValue> {
+    let count = digits(of)?;
+    let ldigits = unpack(left as u32, count)?;
+    let rdigits = unpack(right as u32, count)?;
+
+    let binary = dispatch::<Add>(left, right, of).expect("the width was checked before");
+    let half_carry = utils::nibble(left as u32, 0) + utils::nibble(right as u32, 0) > 0xF;
+
+    let mut decimal = 0u32;
+    let mut carry = false;
+    for (i, (l, r)) in ldigits.iter().zip(rdigits.iter()).enumerate() {
+        let mut digit = l + r + carry as u8;
+        carry = digit > 9;
+        if carry {
+            digit -= 10;
+        }
+        decimal |= (digit as u32) << (i * 4);
+    }
+
+    // the correction is what got added on top of the binary sum
+    let mask = ((1u64 << of) - 1) as u32;
+    let plain = (left as u32 & mask) as u64 + (right as u32 & mask) as u64;
+    let correction = ((decimal as u64 | (carry as u64) << of) - plain) as u32;
+
+    let values = ResultValue::from_raw(decimal, of).expect("the width was checked before");
+    let negative = decimal >> (of - 1) & 1 == 1;
+    let flags = ResultFlags::new(decimal == 0, negative, false, carry);
+
+    Ok(BcdResults::new(
+        binary,
+        Results::new(flags, values),
+        correction,
+        half_carry,
+    ))
+}
@@ -0,0 +1,134 @@
+//! The calculator core on the command line.
+//!
+//! ```text
+//! ct-calc [options] <add|sub|and|or|xor|nand> <left> <right>
+//! ct-calc [options] format <value>
+//! ct-calc [options] table <op>
+//! ct-calc [options] replay <script>
+//! ct-calc [options] repl
+//! ct-calc serve
+//! ```
+
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process,
+};
+
+use ct_calculator::{
+    cli::{self, Args, USAGE},
+    server, Session,
+};
+
+/// where the lines of the REPL are kept between sessions.
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".ct-calc_history"))
+}
+
+fn remember(path: &PathBuf, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+fn repl(args: &Args) -> io::Result<()> {
+    let mut session = Session::new(args.of).expect("the width was checked before");
+    let path = history_path();
+    if let Some(text) = path.as_ref().and_then(|path| fs::read_to_string(path).ok()) {
+        session.extend_history(text.lines().map(String::from));
+    }
+    let mut out = io::stdout().lock();
+    writeln!(out, "type `help` for the commands, `quit` to leave")?;
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        write!(out, "{}> ", session.of())?;
+        out.flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+
+        let known = session.history().len();
+        match session.run(&line) {
+            Ok(answer) if answer.is_empty() => {}
+            Ok(answer) => writeln!(out, "{}", answer)?,
+            Err(err) => writeln!(out, "error: {}", err)?,
+        }
+
+        if let (Some(path), Some(added)) = (&path, session.history().get(known)) {
+            // the history is a convenience, the session goes on without it
+            remember(path, added).ok();
+        }
+    }
+
+    Ok(())
+}
+
+/// answers every line of stdin with a line of JSON, see
+/// [`ct_calculator::server::respond`].
+fn serve() -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(out, "{}", server::respond(&line))?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn print(text: &str) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}", text)?;
+    out.flush()
+}
+
+/// a closed stdout, like behind `| head -1`, ends the program normally.
+fn finish(result: io::Result<()>) {
+    match result {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        finish(print(USAGE));
+        return;
+    }
+
+    let args = match Args::parse(args.into_iter()) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    if args.command == ["repl"] || args.command == ["serve"] {
+        finish(match args.command[0].as_str() {
+            "repl" => repl(&args),
+            _ => serve(),
+        });
+        return;
+    }
+
+    match cli::run(&args) {
+        Ok(out) => finish(print(&out)),
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    }
+}
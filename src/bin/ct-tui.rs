@@ -0,0 +1,335 @@
+//! The web calculator in the terminal, for lab machines without a browser.
+//!
+//! Type the operands as decimal or literals like `0x7F`, `Tab` switches
+//! between them, `Up`/`Down` pick the operation and `Left`/`Right` the
+//! width, `Esc` quits.
+
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ct_calculator::{
+    api::{FormattedValue, ResultFlags, Results, SIGNED_CONDITIONS, UNSIGNED_CONDITIONS},
+    Literal,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+const WIDTHS: [i32; 4] = [4, 8, 16, 32];
+
+/// The operations of the web page, `CMP` and `TST` are `SUB` and `AND`
+/// without keeping the result.
+const OPERATIONS: [(&str, &str); 8] = [
+    ("ADD", "add"),
+    ("SUB", "sub"),
+    ("AND", "and"),
+    ("OR", "or"),
+    ("XOR", "xor"),
+    ("NAND", "nand"),
+    ("CMP", "sub"),
+    ("TST", "and"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Left,
+    Right,
+}
+
+struct App {
+    width: usize,
+    operation: usize,
+    focus: Focus,
+    left: String,
+    right: String,
+}
+
+impl App {
+    fn new() -> Self {
+        Self {
+            width: 0,
+            operation: 0,
+            focus: Focus::Left,
+            left: String::new(),
+            right: String::new(),
+        }
+    }
+
+    fn of(&self) -> i32 {
+        WIDTHS[self.width]
+    }
+
+    fn input(&mut self) -> &mut String {
+        match self.focus {
+            Focus::Left => &mut self.left,
+            Focus::Right => &mut self.right,
+        }
+    }
+
+    /// reads an operand, `None` while it is empty.
+    fn value(&self, input: &str) -> Option<Result<FormattedValue, String>> {
+        if input.trim().is_empty() {
+            return None;
+        }
+        Some(
+            Literal::parse(input, 10, self.of())
+                .map(|literal| literal.get_formatted())
+                .map_err(|err| err.to_string()),
+        )
+    }
+
+    fn results(&self) -> Option<Results> {
+        let left = self.value(&self.left)?.ok()?;
+        let right = self.value(&self.right)?.ok()?;
+        let (_, op) = OPERATIONS[self.operation];
+        ct_calculator::operation(op, left.get_raw() as i32, right.get_raw() as i32, self.of()).ok()
+    }
+
+    /// handles a key, `false` to quit.
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        match code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Enter => {
+                self.focus = match self.focus {
+                    Focus::Left => Focus::Right,
+                    Focus::Right => Focus::Left,
+                }
+            }
+            KeyCode::Up => {
+                self.operation = (self.operation + OPERATIONS.len() - 1) % OPERATIONS.len()
+            }
+            KeyCode::Down => self.operation = (self.operation + 1) % OPERATIONS.len(),
+            // like the web page, a new width starts over
+            KeyCode::Left | KeyCode::Right => {
+                self.width = match code {
+                    KeyCode::Left => self.width.saturating_sub(1),
+                    _ => (self.width + 1).min(WIDTHS.len() - 1),
+                };
+                self.left.clear();
+                self.right.clear();
+            }
+            KeyCode::Backspace => {
+                self.input().pop();
+            }
+            KeyCode::Delete => self.input().clear(),
+            KeyCode::Char(c) => self.input().push(c),
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [widths, values, bottom, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(9),
+            Constraint::Min(12),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let spans: Vec<Span> = WIDTHS
+            .iter()
+            .enumerate()
+            .flat_map(|(i, of)| {
+                let mark = if i == self.width { "(•)" } else { "( )" };
+                [Span::raw(format!("{} {}bit", mark, of)), Span::raw("   ")]
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).block(Block::bordered().title("Width")),
+            widths,
+        );
+
+        let [left, operations, right, result] = Layout::horizontal([
+            Constraint::Ratio(2, 7),
+            Constraint::Ratio(1, 7),
+            Constraint::Ratio(2, 7),
+            Constraint::Ratio(2, 7),
+        ])
+        .areas(values);
+        self.operand(frame, left, "Input", &self.left, self.focus == Focus::Left);
+        self.operations(frame, operations);
+        self.operand(
+            frame,
+            right,
+            "Operand",
+            &self.right,
+            self.focus == Focus::Right,
+        );
+
+        let results = self.results();
+        self.result(frame, result, results.as_ref());
+
+        let flags = results.map(|results| results.get_flags());
+        let [flag_area, unsigned, signed] = Layout::horizontal([
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(3, 8),
+            Constraint::Ratio(3, 8),
+        ])
+        .areas(bottom);
+        self.flags(frame, flag_area, flags.as_ref());
+        conditions(
+            frame,
+            unsigned,
+            "Unsigned Conditionals",
+            &UNSIGNED_CONDITIONS,
+            flags.as_ref(),
+        );
+        conditions(
+            frame,
+            signed,
+            "Signed Conditionals",
+            &SIGNED_CONDITIONS,
+            flags.as_ref(),
+        );
+
+        frame.render_widget(
+            Paragraph::new("Tab: operand  Up/Down: operation  Left/Right: width  Esc: quit").dim(),
+            help,
+        );
+    }
+
+    fn operand(&self, frame: &mut Frame, area: Rect, title: &str, input: &str, focused: bool) {
+        let cursor = if focused { "_" } else { "" };
+        let mut lines = vec![Line::from(format!("> {}{}", input, cursor)).bold()];
+
+        match self.value(input) {
+            None => {}
+            Some(Err(err)) => lines.push(Line::from(err).red()),
+            Some(Ok(value)) => {
+                lines.extend(rows(&[
+                    ("Signed Decimal", value.get_signed()),
+                    ("Unsigned Decimal", value.get_unsigned()),
+                    ("Binary", value.get_bin()),
+                    ("2C", value.get_com()),
+                    ("Hexadecimal", value.get_hex()),
+                ]));
+            }
+        }
+
+        let block = Block::bordered().title(title);
+        let block = if focused {
+            block.border_style(Style::new().yellow())
+        } else {
+            block
+        };
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn operations(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = OPERATIONS
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                if i == self.operation {
+                    Line::from(format!("> {}", name)).add_modifier(Modifier::REVERSED)
+                } else {
+                    Line::from(format!("  {}", name))
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+    }
+
+    fn result(&self, frame: &mut Frame, area: Rect, results: Option<&Results>) {
+        let lines = match results {
+            Some(results) => {
+                let value = results.get_value();
+                rows(&[
+                    ("Signed Decimal", value.get_signed()),
+                    ("Unsigned Decimal", value.get_unsigned()),
+                    ("Binary", value.get_bin()),
+                    ("Hexadecimal", value.get_hex()),
+                ])
+            }
+            None => Vec::new(),
+        };
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Result")),
+            area,
+        );
+    }
+
+    fn flags(&self, frame: &mut Frame, area: Rect, flags: Option<&ResultFlags>) {
+        let bit = |flag: fn(&ResultFlags) -> bool| match flags {
+            Some(flags) if flag(flags) => "1",
+            _ => "0",
+        };
+        let lines = vec![
+            flag_line("Z", "Zero", bit(|f| f.zero)),
+            flag_line("N", "Negative", bit(|f| f.negative)),
+            flag_line("V", "Overflow", bit(|f| f.overflow)),
+            flag_line("C", "Carry", bit(|f| f.carry)),
+            flag_line("B", "Borrow", bit(|f| f.borrow)),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Flags")),
+            area,
+        );
+    }
+}
+
+fn rows(rows: &[(&str, String)]) -> Vec<Line<'static>> {
+    rows.iter()
+        .map(|(name, value)| Line::from(format!("{:<17}{}", name, value)))
+        .collect()
+}
+
+fn flag_line(short: &str, name: &str, bit: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(short.to_string()).bold(),
+        Span::raw(format!(" {:<10}{}", name, bit)),
+    ])
+}
+
+fn conditions(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    codes: &[(&str, &str)],
+    flags: Option<&ResultFlags>,
+) {
+    let lines: Vec<Line> = codes
+        .iter()
+        .map(|(code, name)| {
+            let holds = flags.and_then(|flags| flags.condition(code)) == Some(true);
+            let line = Line::from(vec![
+                Span::raw(format!("{:<4}", code)).bold(),
+                Span::raw(format!("{:<25}{}", name, holds as u8)),
+            ]);
+            if holds {
+                line.green()
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let mut app = App::new();
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.key(key.code, key.modifiers) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,78 @@
+use wasm_bindgen::prelude::*;
+
+use crate::api::Results;
+
+/// A field of `width` bits starting at bit `lsb`.
+struct Field {
+    lsb: u32,
+    width: u32,
+}
+
+impl Field {
+    fn new(lsb: u32, width: u32, of: i32) -> Result<Self, JsValue> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return Err(JsValue::from("unsupported value"));
+        }
+        if width == 0 || lsb.checked_add(width).is_none_or(|end| end > of as u32) {
+            return Err(JsValue::from("bit-field does not fit into the value"));
+        }
+        Ok(Self { lsb, width })
+    }
+
+    /// mask of the field bits, not shifted.
+    fn mask(&self) -> u32 {
+        ((1u64 << self.width) - 1) as u32
+    }
+
+    /// the field bits at their position inside of the value.
+    fn placed(&self) -> u32 {
+        self.mask() << self.lsb
+    }
+}
+
+fn build(raw: u32, of: i32, field: &Field, at: u32) -> Results {
+    // the bit-field instructions set no flags at all, N and Z only describe
+    // the result here and C and V stay clear
+    Results::from_raw(raw, of)
+        .expect("the width was checked before")
+        .with_field(at, field.width)
+}
+
+/// Bit Field Clear, clears `width` bits starting at `lsb`.
+#[wasm_bindgen]
+pub fn bfc(value: i32, lsb: u32, width: u32, of: i32) -> Result<Results, JsValue> {
+    let field = Field::new(lsb, width, of)?;
+    let raw = value as u32 & !field.placed();
+    Ok(build(raw, of, &field, field.lsb))
+}
+
+/// Bit Field Insert, copies the lowest `width` bits of `src` into `dest`
+/// starting at `lsb`.
+#[wasm_bindgen]
+pub fn bfi(dest: i32, src: i32, lsb: u32, width: u32, of: i32) -> Result<Results, JsValue> {
+    let field = Field::new(lsb, width, of)?;
+    let raw = (dest as u32 & !field.placed()) | ((src as u32 & field.mask()) << field.lsb);
+    Ok(build(raw, of, &field, field.lsb))
+}
+
+/// Unsigned Bit Field Extract, the field is zero extended.
+#[wasm_bindgen]
+pub fn ubfx(value: i32, lsb: u32, width: u32, of: i32) -> Result<Results, JsValue> {
+    let field = Field::new(lsb, width, of)?;
+    let raw = (value as u32 >> field.lsb) & field.mask();
+    Ok(build(raw, of, &field, 0))
+}
+
+/// Signed Bit Field Extract, the field is sign extended to the full width.
+#[wasm_bindgen]
+pub fn sbfx(value: i32, lsb: u32, width: u32, of: i32) -> Result<Results, JsValue> {
+    let field = Field::new(lsb, width, of)?;
+    let raw = (value as u32 >> field.lsb) & field.mask();
+    let sign = 1 << (field.width - 1);
+    let raw = if raw & sign != 0 {
+        raw | !field.mask()
+    } else {
+        raw
+    };
+    Ok(build(raw, of, &field, 0))
+}
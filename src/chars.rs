@@ -0,0 +1,102 @@
+use wasm_bindgen::prelude::*;
+
+use crate::api::FormattedValue;
+
+/// the ASCII names of the control characters `0x00..0x20`.
+const CONTROL: [&str; 32] = [
+    "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL", "BS", "HT", "LF", "VT", "FF", "CR",
+    "SO", "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB", "ESC",
+    "FS", "GS", "RS", "US",
+];
+
+/// the Latin-1 names of the control characters `0x80..0xA0`.
+const C1: [&str; 32] = [
+    "PAD", "HOP", "BPH", "NBH", "IND", "NEL", "SSA", "ESA", "HTS", "HTJ", "VTS", "PLD", "PLU",
+    "RI", "SS2", "SS3", "DCS", "PU1", "PU2", "STS", "CCH", "MW", "SPA", "EPA", "SOS", "SGCI",
+    "SCI", "CSI", "ST", "OSC", "PM", "APC",
+];
+
+/// the character of a byte in Latin-1, which is ASCII below `0x80`.
+/// Control and invisible characters are given by name, like `LF` or `SP`.
+pub fn character(byte: u8) -> String {
+    match byte {
+        0x00..=0x1F => CONTROL[byte as usize].to_string(),
+        b' ' => "SP".to_string(),
+        0x7F => "DEL".to_string(),
+        0x80..=0x9F => C1[byte as usize - 0x80].to_string(),
+        0xA0 => "NBSP".to_string(),
+        0xAD => "SHY".to_string(),
+        _ => (byte as char).to_string(),
+    }
+}
+
+/// reads a single character like `A` or `ä`, or the name of a control
+/// character like `LF`, and returns its code point.
+fn code_point(input: &str) -> Result<u32, String> {
+    let mut chars = input.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(c as u32);
+    }
+
+    let name = input.trim().to_uppercase();
+    let named = match name.as_str() {
+        "SP" => Some(0x20),
+        "DEL" => Some(0x7F),
+        "NBSP" => Some(0xA0),
+        "SHY" => Some(0xAD),
+        _ => None,
+    };
+    named
+        .or_else(|| CONTROL.iter().position(|n| *n == name).map(|i| i as u32))
+        .or_else(|| C1.iter().position(|n| *n == name).map(|i| i as u32 + 0x80))
+        .ok_or_else(|| format!("unknown character '{}'", input))
+}
+
+impl FormattedValue {
+    /// builds the value of the code point of a single character or control
+    /// character name, like the character literals of [`crate::Literal`],
+    /// so `€` is `0x20AC`. Below `0x100` this is Latin-1.
+    pub fn from_char_codepoint(input: &str, of: i32) -> Result<Self, String> {
+        let code = code_point(input)?;
+        Self::from_char_value(input, code, of)
+    }
+
+    /// builds the value of the UTF-8 bytes of a single character or control
+    /// character name, the first byte is the most significant one, so `€` is
+    /// `0xE282AC`. This is what [`FormattedValue::get_utf8`] reads back.
+    pub fn from_char_utf8(input: &str, of: i32) -> Result<Self, String> {
+        let code = code_point(input)?;
+        let c = char::from_u32(code).expect("the code point was read from a char");
+        let mut buffer = [0; 4];
+        let value = c
+            .encode_utf8(&mut buffer)
+            .bytes()
+            .fold(0, |value, byte| value << 8 | byte as u32);
+        Self::from_char_value(input, value, of)
+    }
+
+    fn from_char_value(input: &str, value: u32, of: i32) -> Result<Self, String> {
+        if !matches!(of, 8 | 16 | 32) {
+            return Err("characters need 8, 16 or 32 bits".to_string());
+        }
+        if of < 32 && value >> of != 0 {
+            return Err(format!("'{}' does not fit into {} bits", input, of));
+        }
+
+        Ok(Self::of(value as i32, of).expect("the width was checked before"))
+    }
+}
+
+/// Builds the value of a character's code point, see
+/// [`FormattedValue::from_char_codepoint`].
+#[wasm_bindgen]
+pub fn from_char_codepoint(input: &str, of: i32) -> Result<FormattedValue, JsValue> {
+    FormattedValue::from_char_codepoint(input, of).map_err(JsValue::from)
+}
+
+/// Builds the value of a character's UTF-8 bytes, see
+/// [`FormattedValue::from_char_utf8`].
+#[wasm_bindgen]
+pub fn from_char_utf8(input: &str, of: i32) -> Result<FormattedValue, JsValue> {
+    FormattedValue::from_char_utf8(input, of).map_err(JsValue::from)
+}
@@ -0,0 +1,435 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{ResultFlags, Results},
+    exercise::Exercise,
+    operation,
+};
+
+/// What a student claims, every field left `None` is not checked.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answer {
+    pub bin: Option<String>,
+    pub hex: Option<String>,
+    pub signed: Option<String>,
+    pub unsigned: Option<String>,
+    pub negative: Option<bool>,
+    pub zero: Option<bool>,
+    pub carry: Option<bool>,
+    pub overflow: Option<bool>,
+    conditions: Vec<(String, bool)>,
+}
+
+#[wasm_bindgen]
+impl Answer {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// claims that the condition `code`, like `GT`, holds or not.
+    pub fn claim(&mut self, code: &str, holds: bool) {
+        self.conditions.push((code.to_uppercase(), holds));
+    }
+}
+
+/// One field of an answer which is wrong.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    field: String,
+    expected: String,
+    given: String,
+    cause: String,
+}
+
+#[wasm_bindgen]
+impl Mismatch {
+    /// `bin`, `hex`, `signed`, `unsigned`, a flag letter or a condition.
+    #[wasm_bindgen(getter)]
+    pub fn get_field(&self) -> String {
+        self.field.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_expected(&self) -> String {
+        self.expected.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_given(&self) -> String {
+        self.given.clone()
+    }
+
+    /// the likely mistake behind the wrong field.
+    #[wasm_bindgen(getter)]
+    pub fn get_cause(&self) -> String {
+        self.cause.clone()
+    }
+}
+
+/// The checked answer, with every wrong field.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feedback {
+    results: Results,
+    checked: u32,
+    mismatches: Vec<Mismatch>,
+}
+
+#[wasm_bindgen]
+impl Feedback {
+    /// whether every given field is right.
+    #[wasm_bindgen(getter)]
+    pub fn get_correct(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// how many fields were given.
+    #[wasm_bindgen(getter)]
+    pub fn get_checked(&self) -> u32 {
+        self.checked
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_mismatches(&self) -> Vec<Mismatch> {
+        self.mismatches.clone()
+    }
+
+    /// the right answer.
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+
+    /// one line per mismatch, like `C: expected 1, given 0, ...`.
+    pub fn summary(&self) -> String {
+        if self.mismatches.is_empty() {
+            return format!("all {} fields are right", self.checked);
+        }
+        self.mismatches
+            .iter()
+            .map(|m| {
+                format!(
+                    "{}: expected {}, given {}, {}",
+                    m.field, m.expected, m.given, m.cause
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The operation being checked, with the values the causes are guessed
+/// from.
+struct Task<'a> {
+    op: &'a str,
+    of: i32,
+    mask: u32,
+    left: u32,
+    right: u32,
+    results: Results,
+}
+
+impl Task<'_> {
+    fn raw(&self) -> u32 {
+        self.results.get_value().get_raw()
+    }
+
+    fn signed(&self, value: u32) -> i64 {
+        let shift = 64 - self.of;
+        ((value as i64) << shift) >> shift
+    }
+
+    /// the result without dropping the bits above the width, `None` for the
+    /// logical operations which always fit.
+    fn exact(&self, signed: bool) -> Option<i64> {
+        let (left, right) = if signed {
+            (self.signed(self.left), self.signed(self.right))
+        } else {
+            (self.left as i64, self.right as i64)
+        };
+        match self.op {
+            "add" => Some(left + right),
+            "sub" => Some(left - right),
+            _ => None,
+        }
+    }
+
+    /// why `given` is not the result, read as `field`.
+    fn value_cause(&self, field: &str, given: i64, digits: usize) -> String {
+        let raw = self.raw() as i64;
+        let signed = self.signed(self.raw());
+        let is_signed = field == "signed";
+
+        // an overflow is the more helpful explanation
+        if self.exact(is_signed) == Some(given) && (is_signed || given >= 0) {
+            return if is_signed {
+                format!(
+                    "the exact result does not fit into {} bit, V is set",
+                    self.of
+                )
+            } else {
+                format!(
+                    "the exact result does not fit into {} bit, only the lower bits remain",
+                    self.of
+                )
+            };
+        }
+        if is_signed && given == raw && raw != signed {
+            return "this is the unsigned value, the top bit counts as negative".to_string();
+        }
+        if !is_signed && given == signed && raw != signed {
+            return "this is the signed value, read the bits as unsigned".to_string();
+        }
+        if self.op == "sub" {
+            let forgot_one = self.left.wrapping_add(!self.right) & self.mask;
+            let swapped = self.right.wrapping_sub(self.left) & self.mask;
+            let (forgot_one, swapped) = if is_signed {
+                (self.signed(forgot_one), self.signed(swapped))
+            } else {
+                (forgot_one as i64, swapped as i64)
+            };
+            if given == forgot_one {
+                return "the two's complement of the right operand needs the +1".to_string();
+            }
+            if given == swapped {
+                return "the operands are swapped, SUBS computes left - right".to_string();
+            }
+        }
+        let width = match field {
+            "bin" => self.of as usize,
+            "hex" => self.of as usize / 4,
+            _ => usize::MAX,
+        };
+        if digits > width {
+            return format!("more digits than {} bit", self.of);
+        }
+        if field == "bin" && digits == self.of as usize {
+            let reversed = (self.raw().reverse_bits() >> (32 - self.of)) as i64;
+            if given == reversed && reversed != raw {
+                return "the bits are reversed, the MSB comes first".to_string();
+            }
+        }
+        "wrong value".to_string()
+    }
+
+    fn flag_cause(&self, flag: char, given: &Answer, flags: &ResultFlags) -> String {
+        let logical = !matches!(self.op, "add" | "sub");
+        let swapped = flags.carry != flags.overflow
+            && given.carry == Some(flags.overflow)
+            && given.overflow == Some(flags.carry);
+
+        match flag {
+            'C' | 'V' if logical => "the logical operations clear C and V here".to_string(),
+            'C' | 'V' if swapped => {
+                "carry and overflow mixed up, C is the unsigned and V the signed overflow"
+                    .to_string()
+            }
+            'C' if self.op == "sub" => {
+                "carry and borrow mixed up, SUBS sets C=1 when there is no borrow".to_string()
+            }
+            'C' => format!("C is the carry out of bit {}", self.of - 1),
+            'V' => "V is set when two operands of the same sign give a result of the other sign"
+                .to_string(),
+            'N' => "N is the top bit of the result".to_string(),
+            _ => "Z is set when the result is zero".to_string(),
+        }
+    }
+}
+
+/// when a condition holds, in flags.
+fn formula(code: &str) -> &'static str {
+    match code {
+        "EQ" => "Z=1",
+        "NE" => "Z=0",
+        "HS" | "CS" => "C=1",
+        "LO" | "CC" => "C=0",
+        "MI" => "N=1",
+        "PL" => "N=0",
+        "VS" => "V=1",
+        "VC" => "V=0",
+        "HI" => "C=1 and Z=0",
+        "LS" => "C=0 or Z=1",
+        "GE" => "N=V",
+        "LT" => "N!=V",
+        "GT" => "Z=0 and N=V",
+        "LE" => "Z=1 or N!=V",
+        _ => "always",
+    }
+}
+
+/// reads a number as a student may write it, with a prefix, `_` or spaces
+/// between the digits. Returns the value and the number of digits.
+fn read(text: &str, radix: u32) -> Option<(i64, usize)> {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text.as_str()),
+    };
+    let lower = text.to_lowercase();
+    let digits = match radix {
+        2 => lower.strip_prefix("0b").unwrap_or(&lower),
+        16 => lower.strip_prefix("0x").unwrap_or(&lower),
+        _ => &lower,
+    };
+    let value = i64::from_str_radix(digits, radix).ok()?;
+    Some((if negative { -value } else { value }, digits.len()))
+}
+
+fn bit(value: bool) -> String {
+    (value as u8).to_string()
+}
+
+impl Feedback {
+    /// Checks `answer` against the calculator, `left` and `right` are read
+    /// in `of` bit like [`operation`] does. Only an unknown operation or
+    /// width is an error, anything wrong in the answer is a mismatch.
+    pub fn check(
+        op: &str,
+        left: i32,
+        right: i32,
+        of: i32,
+        answer: &Answer,
+    ) -> Result<Self, String> {
+        let results = operation(op, left, right, of)?;
+        let mask = if of == 32 { u32::MAX } else { (1 << of) - 1 };
+        let task = Task {
+            op,
+            of,
+            mask,
+            left: left as u32 & mask,
+            right: right as u32 & mask,
+            results,
+        };
+
+        let value = task.results.get_value();
+        let flags = task.results.get_flags();
+        let mut checked = 0;
+        let mut mismatches = Vec::new();
+
+        let fields = [
+            ("bin", &answer.bin, 2, value.get_bin(), task.raw() as i64),
+            ("hex", &answer.hex, 16, value.get_hex(), task.raw() as i64),
+            (
+                "unsigned",
+                &answer.unsigned,
+                10,
+                value.get_unsigned(),
+                task.raw() as i64,
+            ),
+            (
+                "signed",
+                &answer.signed,
+                10,
+                value.get_signed(),
+                task.signed(task.raw()),
+            ),
+        ];
+        for (field, given, radix, expected, number) in fields {
+            let Some(given) = given else { continue };
+            checked += 1;
+            let cause = match read(given, radix) {
+                None => format!("'{}' is no {} number", given.trim(), field),
+                Some((read, _)) if read == number => continue,
+                Some((read, digits)) => task.value_cause(field, read, digits),
+            };
+            mismatches.push(Mismatch {
+                field: field.to_string(),
+                expected,
+                given: given.trim().to_string(),
+                cause,
+            });
+        }
+
+        let claimed = [
+            ('N', answer.negative, flags.negative),
+            ('Z', answer.zero, flags.zero),
+            ('C', answer.carry, flags.carry),
+            ('V', answer.overflow, flags.overflow),
+        ];
+        for (flag, given, expected) in claimed {
+            let Some(given) = given else { continue };
+            checked += 1;
+            if given != expected {
+                mismatches.push(Mismatch {
+                    field: flag.to_string(),
+                    expected: bit(expected),
+                    given: bit(given),
+                    cause: task.flag_cause(flag, answer, &flags),
+                });
+            }
+        }
+
+        // the flags the student believes in, to tell wrong conditions from
+        // wrong flags
+        let believed = ResultFlags::new(
+            answer.zero.unwrap_or(flags.zero),
+            answer.negative.unwrap_or(flags.negative),
+            answer.overflow.unwrap_or(flags.overflow),
+            answer.carry.unwrap_or(flags.carry),
+        );
+        for (code, given) in &answer.conditions {
+            checked += 1;
+            let Some(expected) = flags.condition(code) else {
+                // a typo of the student is a wrong answer, not a failure
+                mismatches.push(Mismatch {
+                    field: code.clone(),
+                    expected: "-".to_string(),
+                    given: bit(*given),
+                    cause: format!("unknown condition '{}'", code),
+                });
+                continue;
+            };
+            if *given != expected {
+                let cause = if believed.condition(code) == Some(*given) && believed != flags {
+                    "follows from the wrong flags".to_string()
+                } else {
+                    format!("{} holds when {}", code, formula(code))
+                };
+                mismatches.push(Mismatch {
+                    field: code.clone(),
+                    expected: bit(expected),
+                    given: bit(*given),
+                    cause,
+                });
+            }
+        }
+
+        Ok(Self {
+            results: task.results,
+            checked,
+            mismatches,
+        })
+    }
+}
+
+impl Exercise {
+    /// checks the answer to this exercise, see [`Feedback::check`].
+    pub fn check(&self, answer: &Answer) -> Feedback {
+        Feedback::check(
+            &self.get_op(),
+            self.left() as i32,
+            self.right() as i32,
+            self.get_of(),
+            answer,
+        )
+        .expect("the exercise was built from a valid operation")
+    }
+}
+
+/// Checks a student's answer, see [`Feedback::check`].
+#[wasm_bindgen]
+pub fn check_answer(
+    op: &str,
+    left: i32,
+    right: i32,
+    of: i32,
+    answer: &Answer,
+) -> Result<Feedback, JsValue> {
+    Feedback::check(op, left, right, of, answer).map_err(JsValue::from)
+}
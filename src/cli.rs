@@ -0,0 +1,290 @@
+//! The commands of the `ct-calc` binary which answer with text, so they can
+//! be tested without a terminal.
+
+use std::fs;
+
+use serde::Serialize;
+
+use crate::{
+    api::{FormattedValue, ResultFlags},
+    operation, FlagTable, FormatOptions, History, Literal, TableFormat,
+};
+
+/// The help text, also shown after a wrong call.
+pub const USAGE: &str = "usage: ct-calc [options] <add|sub|and|or|xor|nand> <left> <right>
+       ct-calc [options] format <value>
+       ct-calc [options] table <op>
+       ct-calc [options] replay <script>
+       ct-calc [options] repl
+       ct-calc serve             answer JSON requests, one per line
+
+values are decimal or literals like 0x7F, 0b1010, -5, 'A' or 8'hFF
+
+options:
+  -w, --width <4|8|16|32>   the width of the values, 8 by default
+  -j, --json                print JSON instead of a table
+  -g, --group <nibble|byte> group the binary and hex digits
+  -s, --separator <char>    put between the groups, `_` by default
+  -p, --prefix              add 0b and 0x
+  -l, --lowercase           lower case hex digits
+  -r, --ruler               show the bit indices above the binary digits
+  -f, --format <format>     csv, md or html for a 4 or 8 bit table, md by default
+  -h, --help                show this text";
+
+const OPERATIONS: [&str; 6] = ["add", "sub", "and", "or", "xor", "nand"];
+
+/// The options and the command words of one `ct-calc` call.
+pub struct Args {
+    pub of: i32,
+    pub json: bool,
+    pub ruler: bool,
+    pub format: TableFormat,
+    pub options: FormatOptions,
+    pub command: Vec<String>,
+}
+
+impl Args {
+    /// reads the arguments after the program name.
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self {
+            of: 8,
+            json: false,
+            ruler: false,
+            format: TableFormat::Markdown,
+            options: FormatOptions::default(),
+            command: Vec::new(),
+        };
+        let mut group = None;
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            let mut value =
+                |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+            match arg.as_str() {
+                "-w" | "--width" => {
+                    let width = value(&arg)?;
+                    parsed.of = width
+                        .parse()
+                        .ok()
+                        .filter(|of| matches!(of, 4 | 8 | 16 | 32))
+                        .ok_or_else(|| format!("unsupported width '{}'", width))?;
+                }
+                "-j" | "--json" => parsed.json = true,
+                "-g" | "--group" => group = Some(value(&arg)?),
+                "-s" | "--separator" => {
+                    let separator = value(&arg)?;
+                    let mut chars = separator.chars();
+                    parsed.options.separator = match (chars.next(), chars.next()) {
+                        (Some(c), None) => c,
+                        _ => return Err(format!("invalid separator '{}'", separator)),
+                    };
+                }
+                "-p" | "--prefix" => parsed.options.prefix = true,
+                "-l" | "--lowercase" => parsed.options.lowercase = true,
+                "-r" | "--ruler" => parsed.ruler = true,
+                "-f" | "--format" => parsed.format = TableFormat::parse(&value(&arg)?)?,
+                "--" => parsed.command.extend(args.by_ref()),
+                // negative values are no options
+                _ if arg.starts_with("--")
+                    || arg.starts_with('-') && arg[1..].starts_with(char::is_alphabetic) =>
+                {
+                    return Err(format!("unknown option '{}'", arg))
+                }
+                _ => parsed.command.push(arg),
+            }
+        }
+
+        match group.as_deref() {
+            None => {}
+            Some("nibble") => (parsed.options.bin_group, parsed.options.hex_group) = (4, 2),
+            Some("byte") => (parsed.options.bin_group, parsed.options.hex_group) = (8, 2),
+            Some(group) => return Err(format!("unknown grouping '{}'", group)),
+        }
+
+        Ok(parsed)
+    }
+
+    fn value(&self, input: &str) -> Result<FormattedValue, String> {
+        let literal = Literal::parse(input, 10, self.of).map_err(|err| err.to_string())?;
+        Ok(literal.get_formatted())
+    }
+}
+
+/// One value as shown in a row of the table or a JSON object.
+#[derive(Serialize)]
+struct Row {
+    #[serde(skip)]
+    name: &'static str,
+    raw: u32,
+    unsigned: String,
+    signed: String,
+    hex: String,
+    bin: String,
+}
+
+impl Row {
+    fn of(name: &'static str, value: &FormattedValue, options: &FormatOptions) -> Self {
+        let value = value.with_options(options);
+        Self {
+            name,
+            raw: value.get_raw(),
+            unsigned: value.get_unsigned(),
+            signed: value.get_signed(),
+            hex: value.get_hex(),
+            bin: value.get_bin(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FormatJson<'a> {
+    of: i32,
+    value: &'a Row,
+    oct: String,
+    com: String,
+}
+
+#[derive(Serialize)]
+struct CalculationJson<'a> {
+    op: &'a str,
+    of: i32,
+    left: &'a Row,
+    right: &'a Row,
+    result: &'a Row,
+    flags: &'a ResultFlags,
+}
+
+fn json(value: &impl Serialize) -> String {
+    serde_json::to_string(value).expect("the output only holds strings, numbers and flags")
+}
+
+fn table(args: &Args, title: &str, rows: &[Row], ruler: &str) -> String {
+    let width = |header: &str, column: fn(&Row) -> &str| {
+        rows.iter()
+            .map(|row| column(row).chars().count())
+            .chain([header.chars().count()])
+            .max()
+            .unwrap_or(0)
+    };
+    let bin_header = if args.ruler { ruler } else { "bin" };
+    let hex = width("hex", |row| &row.hex);
+    let bin = width(bin_header, |row| &row.bin);
+    let unsigned = width("unsigned", |row| &row.unsigned);
+    let signed = width("signed", |row| &row.signed);
+
+    let mut lines = vec![
+        title.to_string(),
+        format!(
+            "{:8} {:>hex$}  {:bin$}  {:>unsigned$}  {:>signed$}",
+            "", "hex", bin_header, "unsigned", "signed"
+        ),
+    ];
+    for row in rows {
+        lines.push(format!(
+            "{:8} {:>hex$}  {:bin$}  {:>unsigned$}  {:>signed$}",
+            row.name, row.hex, row.bin, row.unsigned, row.signed
+        ));
+    }
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs a command which answers with text, everything but `repl` and
+/// `serve`, which read from stdin.
+pub fn run(args: &Args) -> Result<String, String> {
+    let options = &args.options;
+    let ruler = options.ruler(args.of as usize);
+
+    match args.command.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["format", input] => {
+            let value = args.value(input)?;
+            let row = Row::of("value", &value, options);
+            if args.json {
+                return Ok(json(&FormatJson {
+                    of: args.of,
+                    value: &row,
+                    oct: value.get_oct(),
+                    com: value.get_com(),
+                }));
+            }
+            let title = format!("{} bit, oct {}", args.of, value.get_oct());
+            Ok(table(args, &title, &[row], &ruler))
+        }
+        ["table", op] => Ok(FlagTable::new(op, args.of)?.write(args.format)),
+        ["replay", path] => {
+            let script = fs::read_to_string(path)
+                .map_err(|err| format!("cannot read '{}': {}", path, err))?;
+            let history = History::replay(&script)?;
+            let outputs = history
+                .get_entries()
+                .iter()
+                .map(|entry| {
+                    calculation(
+                        args,
+                        entry.get_of(),
+                        &entry.get_op(),
+                        &entry.get_left(),
+                        &entry.get_right(),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let separator = if args.json { "\n" } else { "\n\n" };
+            Ok(outputs.join(separator))
+        }
+        [op, left, right] if OPERATIONS.contains(&op) => {
+            let left = args.value(left)?;
+            let right = args.value(right)?;
+            calculation(args, args.of, op, &left, &right)
+        }
+        [] => Err("missing command".to_string()),
+        [command, ..]
+            if matches!(command, "format" | "table" | "replay")
+                || OPERATIONS.contains(&command) =>
+        {
+            Err(format!("wrong amount of values for '{}'", command))
+        }
+        [command, ..] => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+/// the table or JSON of one operation in `of` bit.
+fn calculation(
+    args: &Args,
+    of: i32,
+    op: &str,
+    left: &FormattedValue,
+    right: &FormattedValue,
+) -> Result<String, String> {
+    let options = &args.options;
+    let results = operation(op, left.get_raw() as i32, right.get_raw() as i32, of)?;
+    let flags = results.get_flags();
+    let result = FormattedValue::of(results.get_value().get_raw() as i32, of)
+        .expect("the width was checked before");
+
+    let rows = [
+        Row::of("left", left, options),
+        Row::of("right", right, options),
+        Row::of("result", &result, options),
+    ];
+    if args.json {
+        return Ok(json(&CalculationJson {
+            op,
+            of,
+            left: &rows[0],
+            right: &rows[1],
+            result: &rows[2],
+            flags: &flags,
+        }));
+    }
+
+    let ruler = options.ruler(of as usize);
+    let mut out = table(args, &format!("{}, {} bit", op, of), &rows, &ruler);
+    out.push_str(&format!(
+        "\nflags    N={} Z={} C={} V={}",
+        flags.negative as u8, flags.zero as u8, flags.carry as u8, flags.overflow as u8
+    ));
+    Ok(out)
+}
@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+
+use crate::api::Results;
+
+/// Runs `fun` on the lower `of` bits of `value`, the second argument is the
+/// bit width. The answer is formatted with the same width.
+fn unary<F>(value: i32, of: i32, fun: F) -> Result<Results, JsValue>
+where
+    F: Fn(u32, u32) -> u32,
+{
+    if !matches!(of, 4 | 8 | 16 | 32) {
+        return Err(JsValue::from("unsupported value"));
+    }
+    let bits = of as u32;
+    let mask = ((1u64 << bits) - 1) as u32;
+
+    let raw = fun(value as u32 & mask, bits);
+    Ok(Results::from_raw(raw, of).expect("the width was checked before"))
+}
+
+fn leading_zeros(value: u32, bits: u32) -> u32 {
+    value.leading_zeros() - (32 - bits)
+}
+
+macro_rules! counting {
+    ($(#[$meta:meta])* $name:ident |$value:ident, $bits:ident| $body:expr) => {
+        $(#[$meta])*
+        #[wasm_bindgen]
+        pub fn $name(value: i32, of: i32) -> Result<Results, JsValue> {
+            unary(value, of, |$value, $bits| $body)
+        }
+    };
+}
+
+counting!(
+    /// Count Leading Zeros, a zero input gives the width.
+    clz | value,
+    bits | leading_zeros(value, bits)
+);
+
+counting!(
+    /// Reverse Bits, bit 0 is swapped with bit `of - 1` and so on.
+    rbit | value,
+    bits | value.reverse_bits() >> (32 - bits)
+);
+
+counting!(
+    /// Population count, the amount of bits set.
+    popcount | value,
+    _bits | value.count_ones()
+);
+
+counting!(
+    /// Find First Set, the 1-based index of the lowest bit set, 0 if none is.
+    ffs | value,
+    _bits | if value == 0 {
+        0
+    } else {
+        value.trailing_zeros() + 1
+    }
+);
+
+counting!(
+    /// Find Last Set, the 1-based index of the highest bit set, 0 if none is.
+    fls | value,
+    bits | bits - leading_zeros(value, bits)
+);
@@ -0,0 +1,330 @@
+use wasm_bindgen::prelude::*;
+
+use crate::exercise::Exercise;
+
+/// How an exam sheet is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Markdown,
+    /// a whole document, the binary digits in monospace.
+    Latex,
+    /// a whole page, ready to print.
+    Html,
+}
+
+impl SheetFormat {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "latex" | "tex" => Ok(Self::Latex),
+            "html" => Ok(Self::Html),
+            _ => Err(format!("unknown sheet format '{}'", text)),
+        }
+    }
+}
+
+/// A table of a problem, `None` cells are left blank for the answer.
+struct Table {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<Option<String>>>,
+    /// the width of the values, the bin column needs room for every bit.
+    of: i32,
+}
+
+/// A set of problems, written once with blank answer tables and once with
+/// the solutions filled in by the calculator.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exam {
+    title: String,
+    exercises: Vec<Exercise>,
+}
+
+#[wasm_bindgen]
+impl Exam {
+    pub fn new(title: &str) -> Exam {
+        Self {
+            title: title.to_string(),
+            exercises: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, exercise: &Exercise) {
+        self.exercises.push(exercise.clone());
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_len(&self) -> usize {
+        self.exercises.len()
+    }
+
+    /// the sheet for the students, `format` is `markdown`, `latex` or
+    /// `html`.
+    #[wasm_bindgen(js_name = sheet)]
+    pub fn sheet_js(&self, format: &str) -> Result<String, JsValue> {
+        Ok(self.sheet(SheetFormat::parse(format)?))
+    }
+
+    /// the sheet with the answers, `format` is `markdown`, `latex` or
+    /// `html`.
+    #[wasm_bindgen(js_name = solutions)]
+    pub fn solutions_js(&self, format: &str) -> Result<String, JsValue> {
+        Ok(self.solutions(SheetFormat::parse(format)?))
+    }
+}
+
+impl Exam {
+    pub fn exercises(&self) -> &[Exercise] {
+        &self.exercises
+    }
+
+    pub fn sheet(&self, format: SheetFormat) -> String {
+        self.write(format, &self.title, false)
+    }
+
+    pub fn solutions(&self, format: SheetFormat) -> String {
+        self.write(format, &format!("{}: Solutions", self.title), true)
+    }
+
+    fn write(&self, format: SheetFormat, title: &str, solved: bool) -> String {
+        let problems: Vec<(String, Vec<Table>)> = self
+            .exercises
+            .iter()
+            .map(|exercise| (exercise.get_question(), tables(exercise, solved)))
+            .collect();
+        match format {
+            SheetFormat::Markdown => markdown(title, &problems),
+            SheetFormat::Latex => latex(title, &problems),
+            SheetFormat::Html => html(title, &problems),
+        }
+    }
+}
+
+/// the answer tables of an exercise, the values row and the flags.
+fn tables(exercise: &Exercise, solved: bool) -> Vec<Table> {
+    let fill = |value: String| if solved { Some(value) } else { None };
+
+    let values = if exercise.get_targeted() {
+        // one possible choice, any other matching pair is right as well
+        [
+            ("left", exercise.get_left()),
+            ("right", exercise.get_right()),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            vec![
+                Some(name.to_string()),
+                fill(value.get_bin()),
+                fill(value.get_hex()),
+                fill(value.get_unsigned()),
+                fill(value.get_signed()),
+            ]
+        })
+        .collect()
+    } else {
+        let value = exercise.get_results().get_value();
+        vec![vec![
+            Some("result".to_string()),
+            fill(value.get_bin()),
+            fill(value.get_hex()),
+            fill(value.get_unsigned()),
+            fill(value.get_signed()),
+        ]]
+    };
+
+    let flags = exercise.get_results().get_flags();
+    let flags = [flags.negative, flags.zero, flags.carry, flags.overflow]
+        .iter()
+        .map(|flag| fill((*flag as u8).to_string()))
+        .collect();
+
+    vec![
+        Table {
+            header: vec!["", "bin", "hex", "unsigned", "signed"],
+            rows: values,
+            of: exercise.get_of(),
+        },
+        Table {
+            header: vec!["N", "Z", "C", "V"],
+            rows: vec![flags],
+            of: exercise.get_of(),
+        },
+    ]
+}
+
+/// whether a column holds digits best shown in monospace.
+fn mono(header: &str) -> bool {
+    matches!(header, "bin" | "hex")
+}
+
+/// how many spaces a blank cell of a Markdown table gets.
+fn blank(header: &str) -> usize {
+    match header {
+        "bin" => 16,
+        "N" | "Z" | "C" | "V" => 2,
+        _ => 8,
+    }
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn markdown(title: &str, problems: &[(String, Vec<Table>)]) -> String {
+    let mut lines = vec![format!("# {}", markdown_escape(title))];
+    for (number, (question, tables)) in problems.iter().enumerate() {
+        lines.push(String::new());
+        lines.push(format!("## {}. {}", number + 1, markdown_escape(question)));
+        for table in tables {
+            lines.push(String::new());
+            lines.push(format!("| {} |", table.header.join(" | ")));
+            lines.push(format!("|{}", "---|".repeat(table.header.len())));
+            for row in &table.rows {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&table.header)
+                    .map(|(cell, header)| match cell {
+                        Some(cell) if mono(header) => format!("`{}`", cell),
+                        Some(cell) => cell.clone(),
+                        // wide enough to write in once printed
+                        None => "&nbsp;".repeat(blank(header)),
+                    })
+                    .collect();
+                lines.push(format!("| {} |", cells.join(" | ")));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn latex(title: &str, problems: &[(String, Vec<Table>)]) -> String {
+    let mut lines = vec![
+        "\\documentclass{article}".to_string(),
+        "\\begin{document}".to_string(),
+        format!("\\section*{{{}}}", latex_escape(title)),
+        "\\begin{enumerate}".to_string(),
+    ];
+    for (question, tables) in problems {
+        lines.push(format!("\\item {}", latex_escape(question)));
+        for table in tables {
+            // blank columns get room to write, about 2mm per digit
+            let columns: Vec<String> = table
+                .header
+                .iter()
+                .map(|header| match *header {
+                    "" => "l".to_string(),
+                    "bin" => format!("p{{{:.1}cm}}", 0.2 * table.of as f64 + 0.3),
+                    "N" | "Z" | "C" | "V" => "p{0.8cm}".to_string(),
+                    _ => "p{2cm}".to_string(),
+                })
+                .collect();
+            lines.push(String::new());
+            lines.push(format!("\\begin{{tabular}}{{|{}|}}", columns.join("|")));
+            lines.push("\\hline".to_string());
+            lines.push(format!("{} \\\\", table.header.join(" & ")));
+            lines.push("\\hline".to_string());
+            for row in &table.rows {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&table.header)
+                    .map(|(cell, header)| match cell {
+                        Some(cell) if mono(header) => format!("\\texttt{{{}}}", latex_escape(cell)),
+                        Some(cell) => latex_escape(cell),
+                        None => String::new(),
+                    })
+                    .collect();
+                lines.push(format!("{} \\rule{{0pt}}{{4ex}} \\\\", cells.join(" & ")));
+                lines.push("\\hline".to_string());
+            }
+            lines.push("\\end{tabular}".to_string());
+        }
+        lines.push(String::new());
+    }
+    lines.push("\\end{enumerate}".to_string());
+    lines.push("\\end{document}".to_string());
+    lines.join("\n")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(title: &str, problems: &[(String, Vec<Table>)]) -> String {
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "  <meta charset=\"utf-8\">".to_string(),
+        format!("  <title>{}</title>", html_escape(title)),
+        "  <style>".to_string(),
+        "    table { border-collapse: collapse; margin: 0.5em 0; }".to_string(),
+        "    th, td { border: 1px solid black; padding: 0.3em 0.6em; min-width: 3em; height: 1.8em; }"
+            .to_string(),
+        "    .mono { font-family: monospace; min-width: 10em; }".to_string(),
+        "  </style>".to_string(),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("  <h1>{}</h1>", html_escape(title)),
+        "  <ol>".to_string(),
+    ];
+    for (question, tables) in problems {
+        lines.push(format!("    <li>{}", html_escape(question)));
+        for table in tables {
+            lines.push("      <table>".to_string());
+            let header: String = table
+                .header
+                .iter()
+                .map(|header| format!("<th>{}</th>", header))
+                .collect();
+            lines.push(format!("        <tr>{}</tr>", header));
+            for row in &table.rows {
+                let cells: String = row
+                    .iter()
+                    .zip(&table.header)
+                    .map(|(cell, header)| {
+                        let class = if mono(header) { " class=\"mono\"" } else { "" };
+                        let cell = cell.as_deref().map(html_escape).unwrap_or_default();
+                        format!("<td{}>{}</td>", class, cell)
+                    })
+                    .collect();
+                lines.push(format!("        <tr>{}</tr>", cells));
+            }
+            lines.push("      </table>".to_string());
+        }
+        lines.push("    </li>".to_string());
+    }
+    lines.push("  </ol>".to_string());
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines.join("\n")
+}
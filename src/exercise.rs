@@ -0,0 +1,382 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{FormattedValue, ResultFlags, Results},
+    batch::{BatchResults, FLAG_CARRY, FLAG_NEGATIVE, FLAG_OVERFLOW, FLAG_ZERO},
+    operation,
+};
+
+/// how often a random pair is drawn for a target at 16 and 32 bit, the
+/// small widths are searched completely.
+const TRIES: usize = 100_000;
+
+/// SplitMix64, small and fixed forever so a seed always gives the same
+/// exam, unlike a library generator which may change between versions.
+#[derive(Debug, Clone)]
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// a value below `bound`, which is at most `2^32`.
+    fn below(&mut self, bound: u64) -> u32 {
+        // the upper bits are the good ones, and the product fits
+        (((self.next() >> 32) * bound) >> 32) as u32
+    }
+}
+
+/// How hard the operands of a random exercise are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    /// small positive values, no sign bit involved.
+    Easy,
+    /// any values.
+    Medium,
+    /// values where an addition carries, a subtraction borrows or either
+    /// overflows, only for `add` and `sub`.
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "easy" => Ok(Self::Easy),
+            "medium" => Ok(Self::Medium),
+            "hard" => Ok(Self::Hard),
+            _ => Err(format!("unknown difficulty '{}'", text)),
+        }
+    }
+}
+
+/// The flags an exercise asks for, `None` for the ones which do not matter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlagTarget {
+    pub negative: Option<bool>,
+    pub zero: Option<bool>,
+    pub carry: Option<bool>,
+    pub overflow: Option<bool>,
+}
+
+impl FlagTarget {
+    /// reads a target like `C=0 V=1` or `c=0,v=1`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut target = Self::default();
+        for part in text.split([',', ' ']).filter(|part| !part.is_empty()) {
+            let (flag, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' is no flag like C=0", part))?;
+            let value = match value.trim() {
+                "0" => false,
+                "1" => true,
+                _ => return Err(format!("'{}' is no flag like C=0", part)),
+            };
+            let slot = match flag.trim().to_uppercase().as_str() {
+                "N" => &mut target.negative,
+                "Z" => &mut target.zero,
+                "C" => &mut target.carry,
+                "V" => &mut target.overflow,
+                _ => return Err(format!("unknown flag '{}'", flag.trim())),
+            };
+            *slot = Some(value);
+        }
+        if target == Self::default() {
+            return Err("the target has no flags".to_string());
+        }
+        Ok(target)
+    }
+
+    pub fn matches(&self, flags: &ResultFlags) -> bool {
+        self.packed(
+            (flags.negative as u8 * FLAG_NEGATIVE)
+                | (flags.zero as u8 * FLAG_ZERO)
+                | (flags.carry as u8 * FLAG_CARRY)
+                | (flags.overflow as u8 * FLAG_OVERFLOW),
+        )
+    }
+
+    /// whether the packed flags of a [`BatchResults`] match.
+    fn packed(&self, flags: u8) -> bool {
+        [
+            (self.negative, FLAG_NEGATIVE),
+            (self.zero, FLAG_ZERO),
+            (self.carry, FLAG_CARRY),
+            (self.overflow, FLAG_OVERFLOW),
+        ]
+        .iter()
+        .all(|(wanted, flag)| wanted.is_none_or(|wanted| wanted == (flags & flag != 0)))
+    }
+}
+
+impl std::fmt::Display for FlagTarget {
+    /// the set flags like `C=0 and V=1`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            ("N", self.negative),
+            ("Z", self.zero),
+            ("C", self.carry),
+            ("V", self.overflow),
+        ]
+        .iter()
+        .filter_map(|(name, value)| value.map(|value| format!("{}={}", name, value as u8)))
+        .collect();
+        write!(f, "{}", parts.join(" and "))
+    }
+}
+
+/// A practice problem, the answer key is computed by the calculator.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exercise {
+    op: String,
+    of: i32,
+    left: u32,
+    right: u32,
+    target: Option<FlagTarget>,
+    results: Results,
+}
+
+#[wasm_bindgen]
+impl Exercise {
+    /// builds a hand-picked exercise, `None` if the operation or width is
+    /// not supported.
+    pub fn new(op: &str, left: u32, right: u32, of: i32) -> Option<Exercise> {
+        Self::build(op, left, right, of, None).ok()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_op(&self) -> String {
+        self.op.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_of(&self) -> i32 {
+        self.of
+    }
+
+    /// the left operand, for a targeted exercise one possible answer.
+    #[wasm_bindgen(getter)]
+    pub fn get_left(&self) -> FormattedValue {
+        FormattedValue::of(self.left as i32, self.of).expect("the width was checked")
+    }
+
+    /// the right operand, for a targeted exercise one possible answer.
+    #[wasm_bindgen(getter)]
+    pub fn get_right(&self) -> FormattedValue {
+        FormattedValue::of(self.right as i32, self.of).expect("the width was checked")
+    }
+
+    /// the answer key.
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+
+    /// whether the students choose the operands instead of computing.
+    #[wasm_bindgen(getter)]
+    pub fn get_targeted(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// the task as text, like `Choose 8 bit operands so that SUBS sets C=0
+    /// and V=1.`
+    #[wasm_bindgen(getter)]
+    pub fn get_question(&self) -> String {
+        let mnemonic = mnemonic(&self.op);
+        match &self.target {
+            Some(target) => format!(
+                "Choose {} bit operands so that {} sets {}.",
+                self.of, mnemonic, target
+            ),
+            None => format!(
+                "Compute {} 0x{}, 0x{} in {} bit, give the result and NZCV.",
+                mnemonic,
+                self.get_left().get_hex(),
+                self.get_right().get_hex(),
+                self.of
+            ),
+        }
+    }
+}
+
+impl Exercise {
+    fn build(
+        op: &str,
+        left: u32,
+        right: u32,
+        of: i32,
+        target: Option<FlagTarget>,
+    ) -> Result<Self, String> {
+        let results = operation(op, left as i32, right as i32, of)?;
+        let mask = if of == 32 { u32::MAX } else { (1 << of) - 1 };
+        Ok(Self {
+            op: op.to_string(),
+            of,
+            left: left & mask,
+            right: right & mask,
+            target,
+            results,
+        })
+    }
+
+    pub fn left(&self) -> u32 {
+        self.left
+    }
+
+    pub fn right(&self) -> u32 {
+        self.right
+    }
+
+    pub fn target(&self) -> Option<&FlagTarget> {
+        self.target.as_ref()
+    }
+}
+
+/// the instruction setting the flags, `ADDS` for `add`.
+pub(crate) fn mnemonic(op: &str) -> String {
+    match op {
+        "and" => "ANDS".to_string(),
+        "or" => "ORRS".to_string(),
+        "xor" => "EORS".to_string(),
+        _ => format!("{}S", op.to_uppercase()),
+    }
+}
+
+/// Makes exercises from a seed, the same seed always gives the same
+/// exercises in the same order.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Generator {
+    of: i32,
+    random: Random,
+}
+
+#[wasm_bindgen]
+impl Generator {
+    /// `None` if the width is not supported.
+    pub fn new(seed: u32, of: i32) -> Option<Generator> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return None;
+        }
+        Some(Self {
+            of,
+            random: Random::new(seed as u64),
+        })
+    }
+
+    /// a random exercise, `difficulty` is `easy`, `medium` or `hard`.
+    #[wasm_bindgen(js_name = random)]
+    pub fn random_js(&mut self, op: &str, difficulty: &str) -> Result<Exercise, JsValue> {
+        let difficulty = Difficulty::parse(difficulty)?;
+        Ok(self.random(op, difficulty)?)
+    }
+
+    /// operands for which `op` sets the flags of `target`, like `C=0 V=1`.
+    #[wasm_bindgen(js_name = targeted)]
+    pub fn targeted_js(&mut self, op: &str, target: &str) -> Result<Exercise, JsValue> {
+        let target = FlagTarget::parse(target)?;
+        Ok(self.targeted(op, target)?)
+    }
+}
+
+impl Generator {
+    fn count(&self) -> u64 {
+        1 << self.of
+    }
+
+    fn any(&mut self) -> u32 {
+        let count = self.count();
+        self.random.below(count)
+    }
+
+    pub fn random(&mut self, op: &str, difficulty: Difficulty) -> Result<Exercise, String> {
+        match difficulty {
+            Difficulty::Easy => {
+                // both below a quarter, so not even the sum reaches the sign
+                let quarter = self.count() / 4;
+                let left = self.random.below(quarter);
+                let right = self.random.below(quarter);
+                let (left, right) = match op {
+                    // a positive difference
+                    "sub" => (left.max(right), left.min(right)),
+                    _ => (left, right),
+                };
+                Exercise::build(op, left, right, self.of, None)
+            }
+            Difficulty::Medium => {
+                let (left, right) = (self.any(), self.any());
+                Exercise::build(op, left, right, self.of, None)
+            }
+            Difficulty::Hard => {
+                // a carry out of an addition, a borrow (C=0) of a subtraction
+                // or an overflow, the logical operations have neither
+                let hard: fn(&ResultFlags) -> bool = match op {
+                    "add" => |flags| flags.carry || flags.overflow,
+                    "sub" => |flags| !flags.carry || flags.overflow,
+                    _ => return Err(format!("{} has no hard operands, only add and sub", op)),
+                };
+                for _ in 0..TRIES {
+                    let (left, right) = (self.any(), self.any());
+                    let exercise = Exercise::build(op, left, right, self.of, None)?;
+                    if hard(&exercise.results.get_flags()) {
+                        return Ok(exercise);
+                    }
+                }
+                Err(format!("no hard operands found for {}", op))
+            }
+        }
+    }
+
+    pub fn targeted(&mut self, op: &str, target: FlagTarget) -> Result<Exercise, String> {
+        let found = if self.of <= 8 {
+            // every pair, so an impossible target is known for sure
+            let count = self.count() as u32;
+            let left: Vec<u32> = (0..count * count).map(|i| i / count).collect();
+            let right: Vec<u32> = (0..count * count).map(|i| i % count).collect();
+            let batch = BatchResults::run(op, &left, &right, self.of)?;
+            let matching: Vec<usize> = (0..batch.flags().len())
+                .filter(|i| target.packed(batch.flags()[*i]))
+                .collect();
+            if matching.is_empty() {
+                None
+            } else {
+                let index = matching[self.random.below(matching.len() as u64) as usize];
+                Some((left[index], right[index]))
+            }
+        } else {
+            let mut found = None;
+            for _ in 0..TRIES {
+                let (left, right) = (self.any(), self.any());
+                let batch = BatchResults::run(op, &[left], &[right], self.of)?;
+                if target.packed(batch.flags()[0]) {
+                    found = Some((left, right));
+                    break;
+                }
+            }
+            found
+        };
+
+        let (left, right) = found.ok_or_else(|| {
+            format!(
+                "no {} bit operands make {} set {}",
+                self.of,
+                mnemonic(op),
+                target
+            )
+        })?;
+        Exercise::build(op, left, right, self.of, Some(target))
+    }
+}
@@ -0,0 +1,254 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    addition::{Add, Sub},
+    api::Results,
+    dispatch,
+    literal::Literal,
+    logical::{And, Nand, Or, Xor},
+};
+
+/// A single operation done while evaluating an expression.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    op: String,
+    pub left: i32,
+    pub right: i32,
+    results: Results,
+}
+
+#[wasm_bindgen]
+impl Step {
+    /// the name of the operation, like `add` or `nand`.
+    #[wasm_bindgen(getter)]
+    pub fn get_op(&self) -> String {
+        self.op.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+}
+
+/// The final results of an expression and every step taken to get there.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Evaluation {
+    results: Results,
+    steps: Vec<Step>,
+}
+
+#[wasm_bindgen]
+impl Evaluation {
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+
+    /// the operations in the order they were evaluated.
+    #[wasm_bindgen(getter)]
+    pub fn get_steps(&self) -> Vec<Step> {
+        self.steps.clone()
+    }
+}
+
+impl Evaluation {
+    /// Evaluates `expr` with the width `of`. With `strict` all binary
+    /// operators share the same precedence and are evaluated left to right,
+    /// otherwise the C precedence is used (`+ -` before `&` before `^`
+    /// before `|`).
+    pub fn run(expr: &str, of: i32, strict: bool) -> Result<Self, String> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return Err("unsupported value".to_string());
+        }
+
+        let mut parser = Parser {
+            tokens: tokenize(expr, of)?,
+            pos: 0,
+            end: expr.len(),
+            of,
+            strict,
+            steps: Vec::new(),
+        };
+
+        let value = parser.expr(0)?;
+        if let Some((at, _)) = parser.peek() {
+            return Err(format!("unexpected token at {}", at));
+        }
+
+        let results = match parser.steps.last() {
+            Some(step) => step.results.clone(),
+            None => Results::from_raw(value as u32, of).expect("the width was checked before"),
+        };
+
+        Ok(Self {
+            results,
+            steps: parser.steps,
+        })
+    }
+}
+
+/// Evaluates an expression like `(0x7F + 3) & ~0x0F ^ 5`, see
+/// [`Evaluation::run`].
+#[wasm_bindgen]
+pub fn evaluate(expr: &str, of: i32, strict: bool) -> Result<Evaluation, JsValue> {
+    Evaluation::run(expr, of, strict).map_err(JsValue::from)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(u32),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn tokenize(expr: &str, of: i32) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.char_indices().peekable();
+
+    while let Some((at, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' | '-' | '&' | '|' | '^' | '~' => Token::Op(c),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_ascii_alphanumeric() || c == '\'' => {
+                let mut end = at + c.len_utf8();
+                if c == '\'' {
+                    // a character literal runs up to the closing quote
+                    let mut escaped = false;
+                    for (i, next) in chars.by_ref() {
+                        end = i + next.len_utf8();
+                        if next == '\'' && !escaped {
+                            break;
+                        }
+                        escaped = next == '\\' && !escaped;
+                    }
+                } else {
+                    // all others (`0x_FF`, `8'hFF`) up to the next operator
+                    while let Some((i, next)) = chars.peek().copied() {
+                        if !(next.is_ascii_alphanumeric() || next == '_' || next == '\'') {
+                            break;
+                        }
+                        end = i + next.len_utf8();
+                        chars.next();
+                    }
+                }
+                let literal = Literal::parse(&expr[at..end], 10, of)
+                    .map_err(|err| format!("invalid literal at {}: {}", at, err))?;
+                Token::Num(literal.value as u32)
+            }
+            c => return Err(format!("unexpected character '{}' at {}", c, at)),
+        };
+        tokens.push((at, token));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+    of: i32,
+    strict: bool,
+    steps: Vec<Step>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(usize, Token)> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .copied()
+            .ok_or(format!("unexpected end of expression at {}", self.end))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn precedence(&self, op: char) -> Option<u8> {
+        let prec = match op {
+            '|' => 1,
+            '^' => 2,
+            '&' => 3,
+            '+' | '-' => 4,
+            _ => return None,
+        };
+        Some(if self.strict { 1 } else { prec })
+    }
+
+    /// precedence climbing, only binds operators of at least `min`.
+    fn expr(&mut self, min: u8) -> Result<i32, String> {
+        let mut left = self.unary()?;
+
+        while let Some((_, Token::Op(op))) = self.peek() {
+            let prec = match self.precedence(op) {
+                Some(prec) if prec >= min => prec,
+                _ => break,
+            };
+            self.pos += 1;
+
+            let right = self.expr(prec + 1)?;
+            left = self.apply(op, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<i32, String> {
+        match self.next()? {
+            (_, Token::Num(value)) => Ok(value as i32),
+            (_, Token::Op('~')) => {
+                let value = self.unary()?;
+                Ok(self.apply('~', value, value))
+            }
+            (_, Token::Op('-')) => {
+                let value = self.unary()?;
+                Ok(self.apply('-', 0, value))
+            }
+            (_, Token::Op('+')) => self.unary(),
+            (_, Token::Open) => {
+                let value = self.expr(0)?;
+                match self.next()? {
+                    (_, Token::Close) => Ok(value),
+                    (at, _) => Err(format!("expected ')' at {}", at)),
+                }
+            }
+            (at, _) => Err(format!("unexpected token at {}", at)),
+        }
+    }
+
+    /// runs the operation and records it as a step, `~` is done as a NAND of
+    /// the value with itself.
+    fn apply(&mut self, op: char, left: i32, right: i32) -> i32 {
+        let (name, results) = match op {
+            '+' => ("add", dispatch::<Add>(left, right, self.of)),
+            '-' => ("sub", dispatch::<Sub>(left, right, self.of)),
+            '&' => ("and", dispatch::<And>(left, right, self.of)),
+            '|' => ("or", dispatch::<Or>(left, right, self.of)),
+            '^' => ("xor", dispatch::<Xor>(left, right, self.of)),
+            '~' => ("nand", dispatch::<Nand>(left, right, self.of)),
+            _ => unreachable!("the tokenizer only knows these operators"),
+        };
+        let results = results.expect("the width was checked before");
+        let value = results.get_value().get_raw() as i32;
+
+        self.steps.push(Step {
+            op: name.to_string(),
+            left,
+            right,
+            results,
+        });
+
+        value
+    }
+}
@@ -0,0 +1,201 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    addition::{Add, Sub},
+    api::{FixedResults, ResultFlags, ResultValue, Results},
+    dispatch,
+    float::exact,
+};
+
+/// A fixed-point format like `Q1.15` or `UQ8.8`, the integer bits include
+/// the sign bit, so `Q1.15` has 16 bits. `Q15` is short for `Q1.15` at 16
+/// bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QFormat {
+    pub signed: bool,
+    pub int: u32,
+    pub frac: u32,
+}
+
+impl QFormat {
+    /// reads the format, the total amount of bits has to be `of`.
+    pub fn parse(text: &str, of: i32) -> Result<Self, String> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return Err("unsupported value".to_string());
+        }
+        let invalid = || format!("invalid Q format '{}'", text);
+
+        let upper = text.trim().to_uppercase();
+        let (signed, rest) = match upper.strip_prefix("UQ") {
+            Some(rest) => (false, rest),
+            None => (true, upper.strip_prefix('Q').ok_or_else(invalid)?),
+        };
+
+        let (int, frac) = match rest.split_once('.') {
+            Some((int, frac)) => (
+                int.parse::<u32>().map_err(|_| invalid())?,
+                frac.parse::<u32>().map_err(|_| invalid())?,
+            ),
+            None => {
+                let frac = rest.parse::<u32>().map_err(|_| invalid())?;
+                ((of as u32).checked_sub(frac).ok_or_else(invalid)?, frac)
+            }
+        };
+
+        let bits = int.checked_add(frac).ok_or_else(invalid)?;
+        if bits != of as u32 {
+            return Err(format!("{} has {} bits, not {}", text, bits, of));
+        }
+        if signed && int == 0 {
+            return Err(format!("{} has no room for the sign bit", text));
+        }
+
+        Ok(Self { signed, int, frac })
+    }
+
+    fn bits(&self) -> u32 {
+        self.int + self.frac
+    }
+
+    /// the integer value of the pattern, sign extended if signed.
+    fn value(&self, raw: u32) -> i64 {
+        let bits = self.bits();
+        let raw = raw as u64 & ((1u64 << bits) - 1);
+        if self.signed && raw >> (bits - 1) & 1 == 1 {
+            raw as i64 - (1i64 << bits)
+        } else {
+            raw as i64
+        }
+    }
+
+    fn range(&self) -> (i64, i64) {
+        let bits = self.bits();
+        if self.signed {
+            (-(1i64 << (bits - 1)), (1i64 << (bits - 1)) - 1)
+        } else {
+            (0, (1i64 << bits) - 1)
+        }
+    }
+
+    /// the exact real value of the pattern.
+    pub fn real(&self, raw: u32) -> String {
+        let value = self.value(raw);
+        let real = exact(value.unsigned_abs(), -(self.frac as i32));
+        if value < 0 {
+            format!("-{}", real)
+        } else {
+            real
+        }
+    }
+}
+
+/// How the bits shifted out of a product are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// towards negative infinity, a plain arithmetic shift.
+    Floor,
+    /// towards zero, like a C integer division.
+    Zero,
+    /// to nearest, ties away from zero.
+    Nearest,
+    /// to nearest, ties to even.
+    Even,
+}
+
+impl Rounding {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "floor" | "truncate" => Ok(Self::Floor),
+            "zero" => Ok(Self::Zero),
+            "nearest" => Ok(Self::Nearest),
+            "even" => Ok(Self::Even),
+            _ => Err(format!("unknown rounding mode '{}'", text)),
+        }
+    }
+
+    /// divides `value` by `2^shift`, rounded.
+    fn shift(&self, value: i128, shift: u32) -> i128 {
+        if shift == 0 {
+            return value;
+        }
+        let floor = value >> shift;
+        let rest = value - (floor << shift);
+        let half = 1i128 << (shift - 1);
+        let up = match self {
+            Self::Floor => false,
+            Self::Zero => value < 0 && rest != 0,
+            Self::Nearest => rest > half || (rest == half && value >= 0),
+            Self::Even => rest > half || (rest == half && floor & 1 == 1),
+        };
+        floor + up as i128
+    }
+}
+
+impl FixedResults {
+    /// runs a Q format operation, `rounding` is only used by `mul`.
+    pub fn run(
+        op: &str,
+        left: i32,
+        right: i32,
+        format: &str,
+        of: i32,
+        rounding: &str,
+    ) -> Result<Self, String> {
+        let q = QFormat::parse(format, of)?;
+        let rounding = Rounding::parse(rounding)?;
+
+        let (results, overflow, precision_loss) = match op {
+            "add" | "sub" => {
+                // the same scaling on both sides, the adder does the work
+                let results = match op {
+                    "add" => dispatch::<Add>(left, right, of),
+                    _ => dispatch::<Sub>(left, right, of),
+                }
+                .expect("the width was checked before");
+                let flags = results.get_flags();
+                let overflow = if q.signed {
+                    flags.overflow
+                } else if op == "add" {
+                    flags.carry
+                } else {
+                    flags.borrow
+                };
+                (results, overflow, false)
+            }
+            "mul" => {
+                let product = q.value(left as u32) as i128 * q.value(right as u32) as i128;
+                let value = rounding.shift(product, q.frac);
+                let precision_loss = value << q.frac != product;
+                let (min, max) = q.range();
+                let overflow = value < min as i128 || value > max as i128;
+
+                let raw = value as u32;
+                let values = ResultValue::from_raw(raw, of).expect("the width was checked before");
+                let raw = values.get_raw();
+                let negative = raw >> (of - 1) & 1 == 1;
+                let flags = ResultFlags::new(raw == 0, negative, overflow, false);
+                (Results::new(flags, values), overflow, precision_loss)
+            }
+            _ => return Err(format!("unknown operation '{}'", op)),
+        };
+
+        let real = q.real(results.get_value().get_raw());
+        Ok(Self::new(results, real, overflow, precision_loss))
+    }
+}
+
+/// Runs `add`, `sub` or `mul` on two patterns in the Q format `format`, like
+/// `Q1.15`. `rounding` is one of `floor`, `zero`, `nearest` or `even`.
+#[wasm_bindgen]
+pub fn fixed(
+    op: &str,
+    left: i32,
+    right: i32,
+    format: &str,
+    of: i32,
+    rounding: &str,
+) -> Result<FixedResults, JsValue> {
+    FixedResults::run(op, left, right, format, of, rounding).map_err(JsValue::from)
+}
@@ -0,0 +1,314 @@
+use num::{BigUint, Integer, One, ToPrimitive};
+use wasm_bindgen::prelude::*;
+
+/// The layout of an IEEE 754 binary format.
+struct Layout {
+    exponent: u32,
+    mantissa: u32,
+}
+
+impl Layout {
+    fn of(of: i32) -> Option<Self> {
+        match of {
+            16 => Some(Self {
+                exponent: 5,
+                mantissa: 10,
+            }),
+            32 => Some(Self {
+                exponent: 8,
+                mantissa: 23,
+            }),
+            64 => Some(Self {
+                exponent: 11,
+                mantissa: 52,
+            }),
+            _ => None,
+        }
+    }
+
+    fn bias(&self) -> i32 {
+        (1 << (self.exponent - 1)) - 1
+    }
+
+    fn max_exponent(&self) -> u32 {
+        (1 << self.exponent) - 1
+    }
+}
+
+/// A bit pattern interpreted as IEEE 754 half (16), single (32) or double
+/// (64) precision float.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "checked::Float")
+)]
+pub struct FloatValue {
+    pub bits: u64,
+    pub of: i32,
+    pub sign: bool,
+    /// the exponent field as it is stored.
+    pub biased_exponent: u32,
+    /// the exponent the significand is scaled with, `1 - bias` for
+    /// subnormals, meaningless for infinities and NaN.
+    pub exponent: i32,
+    /// the stored fraction bits, without the implicit leading one.
+    pub mantissa: u64,
+    class: String,
+    decimal: String,
+}
+
+#[wasm_bindgen]
+impl FloatValue {
+    /// one of `zero`, `subnormal`, `normal`, `infinite` or `nan`.
+    #[wasm_bindgen(getter)]
+    pub fn get_class(&self) -> String {
+        self.class.clone()
+    }
+
+    /// the exact decimal value, without any rounding.
+    #[wasm_bindgen(getter)]
+    pub fn get_decimal(&self) -> String {
+        self.decimal.clone()
+    }
+}
+
+impl FloatValue {
+    /// interprets the lower `of` bits of `bits`, `None` if `of` is not one
+    /// of 16, 32 or 64.
+    pub fn of(bits: u64, of: i32) -> Option<Self> {
+        let layout = Layout::of(of)?;
+        let bits = if of == 64 {
+            bits
+        } else {
+            bits & ((1 << of) - 1)
+        };
+
+        let sign = bits >> (of - 1) & 1 == 1;
+        let biased_exponent = (bits >> layout.mantissa) as u32 & layout.max_exponent();
+        let mantissa = bits & ((1 << layout.mantissa) - 1);
+
+        let (class, exponent, significand) = match biased_exponent {
+            0 if mantissa == 0 => ("zero", 1 - layout.bias(), 0),
+            0 => ("subnormal", 1 - layout.bias(), mantissa),
+            e if e == layout.max_exponent() && mantissa == 0 => ("infinite", 0, 0),
+            e if e == layout.max_exponent() => ("nan", 0, 0),
+            e => (
+                "normal",
+                e as i32 - layout.bias(),
+                mantissa | 1 << layout.mantissa,
+            ),
+        };
+
+        let decimal = match class {
+            "infinite" => "inf".to_string(),
+            "nan" => "NaN".to_string(),
+            _ => exact(significand, exponent - layout.mantissa as i32),
+        };
+        let decimal = if sign && class != "nan" {
+            format!("-{}", decimal)
+        } else {
+            decimal
+        };
+
+        Some(Self {
+            bits,
+            of,
+            sign,
+            biased_exponent,
+            exponent,
+            mantissa,
+            class: class.to_string(),
+            decimal,
+        })
+    }
+
+    /// Reads a decimal float like `-1.5e3`, `inf` or `nan` and rounds it to
+    /// the nearest value of the format (ties to even).
+    pub fn parse(input: &str, of: i32) -> Result<Self, String> {
+        let input = input.trim();
+        let bits = match of {
+            16 => to_half(input)? as u64,
+            32 => input
+                .parse::<f32>()
+                .map_err(|_| format!("invalid float '{}'", input))?
+                .to_bits() as u64,
+            64 => parse_f64(input)?.to_bits(),
+            _ => return Err("unsupported value".to_string()),
+        };
+        Ok(Self::of(bits, of).expect("the width was checked before"))
+    }
+}
+
+/// The plain shape of a float, a deserialized value is only accepted if its
+/// fields agree with its bits.
+#[cfg(feature = "serde")]
+mod checked {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Float {
+        bits: u64,
+        of: i32,
+        sign: bool,
+        biased_exponent: u32,
+        exponent: i32,
+        mantissa: u64,
+        class: String,
+        decimal: String,
+    }
+
+    impl TryFrom<Float> for super::FloatValue {
+        type Error = String;
+
+        fn try_from(float: Float) -> Result<Self, String> {
+            let value = Self {
+                bits: float.bits,
+                of: float.of,
+                sign: float.sign,
+                biased_exponent: float.biased_exponent,
+                exponent: float.exponent,
+                mantissa: float.mantissa,
+                class: float.class,
+                decimal: float.decimal,
+            };
+            match Self::of(value.bits, value.of) {
+                Some(expected) if expected == value => Ok(value),
+                _ => Err(format!(
+                    "the float does not match its bits {:#X}",
+                    value.bits
+                )),
+            }
+        }
+    }
+}
+
+fn parse_f64(input: &str) -> Result<f64, String> {
+    input
+        .parse::<f64>()
+        .map_err(|_| format!("invalid float '{}'", input))
+}
+
+/// the exact decimal representation of `significand * 2^exponent`.
+pub(crate) fn exact(significand: u64, exponent: i32) -> String {
+    if exponent >= 0 {
+        return (BigUint::from(significand) << exponent as usize).to_string();
+    }
+
+    // significand / 2^k == significand * 5^k / 10^k
+    let k = (-exponent) as usize;
+    let mut five = BigUint::one();
+    for _ in 0..k {
+        five *= 5u32;
+    }
+    let digits = (BigUint::from(significand) * five).to_string();
+    let digits = format!(
+        "{}{}",
+        "0".repeat((k + 1).saturating_sub(digits.len())),
+        digits
+    );
+
+    let (int, frac) = digits.split_at(digits.len() - k);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{}.{}", int, frac)
+    }
+}
+
+/// rounds the decimal `input` to the nearest half precision bit pattern,
+/// from its exact value, a double in between would round twice.
+fn to_half(input: &str) -> Result<u16, String> {
+    let value = parse_f64(input)?;
+    let sign = if value.is_sign_negative() { 0x8000 } else { 0 };
+    if value.is_nan() {
+        return Ok(sign | 0x7E00);
+    }
+    // far beyond the half range, the double is exact enough for both
+    if value.is_infinite() {
+        return Ok(sign | 0x7C00);
+    }
+    if value == 0.0 {
+        return Ok(sign);
+    }
+
+    // the exact value is digits * 10^exponent, written as num / den
+    let lower = input.to_lowercase();
+    let (mantissa, exponent) = match lower.split_once('e') {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<i64>()
+                .map_err(|_| format!("invalid float '{}'", input))?,
+        ),
+        None => (lower.as_str(), 0),
+    };
+    let mantissa = mantissa.trim_start_matches(['+', '-']);
+    let fraction = mantissa.split_once('.').map_or(0, |(_, frac)| frac.len());
+    let digits: BigUint = mantissa
+        .replace('.', "")
+        .parse()
+        .map_err(|_| format!("invalid float '{}'", input))?;
+    let exponent = exponent - fraction as i64;
+    let ten = BigUint::from(10u32);
+    let (num, den) = if exponent >= 0 {
+        (digits * ten.pow(exponent as u32), BigUint::one())
+    } else {
+        (digits, ten.pow(exponent.unsigned_abs() as u32))
+    };
+
+    // num * 2^k / den as integer and remainder
+    let scaled = |k: i64| {
+        let (num, den) = if k >= 0 {
+            (&num << k as usize, den.clone())
+        } else {
+            (num.clone(), &den << k.unsigned_abs() as usize)
+        };
+        let (quotient, rest) = num.div_rem(&den);
+        (quotient, rest, den)
+    };
+
+    // the k which scales the value to 11 significant bits, at most 24 as
+    // the smallest subnormal is 2^-24
+    let mut k = 10 - (num.bits() as i64 - den.bits() as i64);
+    while scaled(k).0.bits() > 11 {
+        k -= 1;
+    }
+    while scaled(k).0.bits() < 11 {
+        k += 1;
+    }
+    let k = k.min(24);
+
+    // an exponent of the half of at most 15 has k >= -5
+    if k < -5 {
+        return Ok(sign | 0x7C00);
+    }
+    let (quotient, rest, den) = scaled(k);
+    let quotient = quotient.to_u64().expect("at most 11 bits");
+    let twice = rest << 1usize;
+    let up = twice > den || (twice == den && quotient % 2 == 1);
+    let significand = quotient + up as u64;
+
+    // a significand rounded up to 2^11 carries into the exponent
+    let pattern = if k == 24 {
+        significand
+    } else {
+        (((25 - k) as u64) << 10) + significand - (1 << 10)
+    };
+    Ok(sign | pattern.min(0x7C00) as u16)
+}
+
+/// Interprets a bit pattern as IEEE 754 float with `of` 16, 32 or 64 bits.
+#[wasm_bindgen]
+pub fn interpret_float(bits: u64, of: i32) -> Result<FloatValue, JsValue> {
+    FloatValue::of(bits, of).ok_or_else(|| JsValue::from("unsupported value"))
+}
+
+/// Rounds a decimal float to its IEEE 754 bit pattern with `of` 16, 32 or 64
+/// bits.
+#[wasm_bindgen]
+pub fn parse_float(input: &str, of: i32) -> Result<FloatValue, JsValue> {
+    FloatValue::parse(input, of).map_err(JsValue::from)
+}
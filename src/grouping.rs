@@ -0,0 +1,172 @@
+use wasm_bindgen::prelude::*;
+
+use crate::api::{FormattedValue, ResultValue, Results};
+
+/// How the binary and hex digits are written, the default is the plain
+/// unbroken run of upper case digits.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatOptions {
+    /// binary digits per group, counted from the least significant one,
+    /// like 4 (nibbles) or 8 (bytes), 0 does not group.
+    pub bin_group: u32,
+    /// hex digits per group, like 2 (bytes), 0 does not group.
+    pub hex_group: u32,
+    /// put between the groups, like `_`, `'` or a space.
+    pub separator: char,
+    /// adds `0b` and `0x`.
+    pub prefix: bool,
+    pub lowercase: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            bin_group: 0,
+            hex_group: 0,
+            separator: '_',
+            prefix: false,
+            lowercase: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl FormatOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// nibble grouped binary and byte grouped hex, like `1010_0101`.
+    pub fn nibbles(separator: char) -> Self {
+        Self {
+            bin_group: 4,
+            hex_group: 2,
+            separator,
+            ..Self::default()
+        }
+    }
+
+    /// byte grouped binary and hex, like `10100101 11110000`.
+    pub fn bytes(separator: char) -> Self {
+        Self {
+            bin_group: 8,
+            hex_group: 2,
+            separator,
+            ..Self::default()
+        }
+    }
+}
+
+impl FormatOptions {
+    /// splits `digits` into groups of `size`, starting at the right.
+    fn group(&self, digits: &str, size: u32) -> String {
+        let size = size as usize;
+        if size == 0 {
+            return digits.to_string();
+        }
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(size) {
+                grouped.push(self.separator);
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+
+    pub fn bin(&self, bin: &str) -> String {
+        let prefix = if self.prefix { "0b" } else { "" };
+        format!("{}{}", prefix, self.group(bin, self.bin_group))
+    }
+
+    pub fn hex(&self, hex: &str) -> String {
+        let prefix = if self.prefix { "0x" } else { "" };
+        let hex = if self.lowercase {
+            hex.to_lowercase()
+        } else {
+            hex.to_string()
+        };
+        format!("{}{}", prefix, self.group(&hex, self.hex_group))
+    }
+
+    /// a line to put above [`FormatOptions::bin`], the index of the most
+    /// significant bit of every group is written above it. Without grouping
+    /// every byte is marked.
+    pub fn ruler(&self, bits: usize) -> String {
+        let size = match self.bin_group as usize {
+            0 => 8,
+            size => size,
+        };
+        let mut ruler = String::new();
+        if self.prefix {
+            ruler.push_str("  ");
+        }
+        let bin = self.group(&"0".repeat(bits), self.bin_group);
+        let mut bit = bits;
+        let mut skip = 0;
+        for c in bin.chars() {
+            if c == self.separator && self.bin_group != 0 {
+                ruler.push(' ');
+                continue;
+            }
+            bit -= 1;
+            if skip > 0 {
+                skip -= 1;
+            } else if (bit + 1).is_multiple_of(size) || bit + 1 == bits {
+                let index = bit.to_string();
+                skip = index.len() - 1;
+                ruler.push_str(&index);
+            } else {
+                ruler.push(' ');
+            }
+        }
+        ruler.trim_end().to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl Results {
+    /// the results with the binary and hex digits of the value written with
+    /// `options`, see [`ResultValue::with_options`].
+    pub fn with_options(&self, options: &FormatOptions) -> Self {
+        let values = self.get_value().with_options(options);
+        self.clone().with_values(values)
+    }
+}
+
+macro_rules! grouped {
+    ($type:ident) => {
+        #[wasm_bindgen]
+        impl $type {
+            /// the binary digits written with `options`, like `0b1010_0101`.
+            pub fn bin_with(&self, options: &FormatOptions) -> String {
+                options.bin(&self.bin)
+            }
+
+            /// the hex digits written with `options`, like `0xa5`.
+            pub fn hex_with(&self, options: &FormatOptions) -> String {
+                options.hex(&self.hex)
+            }
+
+            /// the bit indices to show above [`Self::bin_with`].
+            pub fn ruler(&self, options: &FormatOptions) -> String {
+                options.ruler(self.bin.len())
+            }
+
+            /// the value with [`Self::get_bin`] and [`Self::get_hex`]
+            /// written with `options`, the other notations keep the plain
+            /// digits.
+            pub fn with_options(&self, options: &FormatOptions) -> Self {
+                let mut value = self.clone();
+                value.options = *options;
+                value
+            }
+        }
+    };
+}
+
+grouped!(FormattedValue);
+grouped!(ResultValue);
@@ -0,0 +1,224 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{FormattedValue, Results},
+    literal::Literal,
+    operation,
+};
+
+/// the first line of a script, to tell it from other text files.
+const HEADER: &str = "# ct-calc history";
+
+/// One evaluated operation.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    op: String,
+    of: i32,
+    left: u32,
+    right: u32,
+    results: Results,
+}
+
+#[wasm_bindgen]
+impl Entry {
+    #[wasm_bindgen(getter)]
+    pub fn get_op(&self) -> String {
+        self.op.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_of(&self) -> i32 {
+        self.of
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_left(&self) -> FormattedValue {
+        FormattedValue::of(self.left as i32, self.of).expect("the width was checked")
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_right(&self) -> FormattedValue {
+        FormattedValue::of(self.right as i32, self.of).expect("the width was checked")
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_results(&self) -> Results {
+        self.results.clone()
+    }
+}
+
+impl Entry {
+    fn new(op: &str, left: i32, right: i32, of: i32) -> Result<Self, String> {
+        let results = operation(op, left, right, of)?;
+        let mask = if of == 32 { u32::MAX } else { (1 << of) - 1 };
+        Ok(Self {
+            op: op.to_string(),
+            of,
+            left: left as u32 & mask,
+            right: right as u32 & mask,
+            results,
+        })
+    }
+
+    /// the line of a script, like `add 8 0x7F 0x01`.
+    fn line(&self) -> String {
+        format!(
+            "{} {} 0x{} 0x{}",
+            self.op,
+            self.of,
+            self.get_left().get_hex(),
+            self.get_right().get_hex()
+        )
+    }
+}
+
+/// Every operation evaluated so far, to step back and forth through the
+/// examples of a lecture. Recording after an undo drops what was undone.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<Entry>,
+    /// how many entries are not undone.
+    position: usize,
+}
+
+#[wasm_bindgen]
+impl History {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// evaluates `op` and records it, see [`History::record`].
+    #[wasm_bindgen(js_name = record)]
+    pub fn record_js(
+        &mut self,
+        op: &str,
+        left: i32,
+        right: i32,
+        of: i32,
+    ) -> Result<Results, JsValue> {
+        self.record(op, left, right, of).map_err(JsValue::from)
+    }
+
+    /// reads a script of [`History::script`], see [`History::replay`].
+    #[wasm_bindgen(js_name = replay)]
+    pub fn replay_js(script: &str) -> Result<History, JsValue> {
+        Self::replay(script).map_err(JsValue::from)
+    }
+
+    /// steps back, `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        true
+    }
+
+    /// steps forward again, `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if self.position == self.entries.len() {
+            return false;
+        }
+        self.position += 1;
+        true
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn get_can_redo(&self) -> bool {
+        self.position < self.entries.len()
+    }
+
+    /// the entry to show, `None` when everything is undone.
+    #[wasm_bindgen(getter)]
+    pub fn get_current(&self) -> Option<Entry> {
+        self.position
+            .checked_sub(1)
+            .map(|i| self.entries[i].clone())
+    }
+
+    /// the entries up to the current one.
+    #[wasm_bindgen(getter)]
+    pub fn get_entries(&self) -> Vec<Entry> {
+        self.entries[..self.position].to_vec()
+    }
+
+    /// The entries up to the current one as text, one operation per line,
+    /// for [`History::replay`] or `ct-calc replay`.
+    pub fn script(&self) -> String {
+        let mut lines = vec![HEADER.to_string()];
+        lines.extend(self.entries[..self.position].iter().map(Entry::line));
+        lines.join("\n")
+    }
+}
+
+impl History {
+    /// Evaluates `op` like [`operation`] and records it. The same operation
+    /// twice in a row is recorded once.
+    pub fn record(&mut self, op: &str, left: i32, right: i32, of: i32) -> Result<Results, String> {
+        let entry = Entry::new(op, left, right, of)?;
+        let results = entry.results.clone();
+        if self.get_current().as_ref() != Some(&entry) {
+            self.entries.truncate(self.position);
+            self.entries.push(entry);
+            self.position += 1;
+        }
+        Ok(results)
+    }
+
+    /// Evaluates a script again, every line is `<op> <width> <left> <right>`
+    /// with literals like `0x7F` or `-5`. Empty lines and lines starting
+    /// with `#` are skipped.
+    pub fn replay(script: &str) -> Result<Self, String> {
+        let mut history = Self::new();
+        for (number, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            history
+                .replay_line(line)
+                .map_err(|err| format!("line {}: {}", number + 1, err))?;
+        }
+        Ok(history)
+    }
+
+    fn replay_line(&mut self, line: &str) -> Result<(), String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let [op, width, left, right] = words[..] else {
+            return Err(format!(
+                "expected '<op> <width> <left> <right>', found '{}'",
+                line
+            ));
+        };
+        let of = width
+            .parse::<i32>()
+            .ok()
+            .filter(|of| matches!(of, 4 | 8 | 16 | 32))
+            .ok_or_else(|| format!("unsupported width '{}'", width))?;
+        let operand = |text: &str| {
+            Literal::parse(text, 10, of)
+                .map(|literal| literal.value)
+                .map_err(|err| err.to_string())
+        };
+        self.record(op, operand(left)?, operand(right)?, of)?;
+        Ok(())
+    }
+
+    /// the amount of entries up to the current one, undone entries which
+    /// can still be redone are not counted, like in [`History::get_entries`].
+    pub fn len(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == 0
+    }
+}
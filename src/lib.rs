@@ -1,12 +1,57 @@
 pub mod api;
+#[cfg(feature = "serde")]
+pub mod cli;
+#[cfg(feature = "serde")]
+pub mod server;
 pub mod utils;
 
 mod addition;
+mod batch;
+mod bcd;
+mod bitfield;
+mod chars;
+mod check;
+mod counting;
+mod exam;
+mod exercise;
+mod expression;
+mod fixed;
+mod float;
+mod grouping;
+mod history;
+mod literal;
 mod logical;
+mod memory;
+mod object;
+mod repl;
+mod simd;
+mod table;
 
 use addition::{Add, Sub};
-pub use api::{format, Results};
-use logical::{And, Nand, Or, Xor};
+pub use api::{format, FormatInput, Results};
+pub use batch::{
+    batch, batch_unsigned, BatchResults, FLAG_CARRY, FLAG_NEGATIVE, FLAG_OVERFLOW, FLAG_ZERO,
+};
+pub use bcd::{bcd_add, bcd_decode, bcd_encode};
+pub use bitfield::{bfc, bfi, sbfx, ubfx};
+pub use chars::{from_char_codepoint, from_char_utf8};
+pub use check::{check_answer, Answer, Feedback, Mismatch};
+pub use counting::{clz, ffs, fls, popcount, rbit};
+pub use exam::{Exam, SheetFormat};
+pub use exercise::{Difficulty, Exercise, FlagTarget, Generator};
+pub use expression::{evaluate, Evaluation, Step};
+pub use fixed::{fixed, QFormat, Rounding};
+pub use float::{interpret_float, parse_float, FloatValue};
+pub use grouping::FormatOptions;
+pub use history::{Entry, History};
+pub use literal::{parse, Literal, ParseError};
+pub use logical::{logic, logic_by_name, logic_table};
+use logical::{And, Imply, Nand, Nor, Or, Orn, Xnor, Xor};
+pub use memory::from_memory;
+pub use object::calculate;
+pub use repl::Session;
+pub use simd::{sadd16, sadd8, uadd16, uadd8};
+pub use table::{flag_table, FlagTable, TableFormat};
 use wasm_bindgen::prelude::*;
 
 #[cfg(feature = "wee_alloc")]
@@ -20,24 +65,69 @@ trait Supported {
     fn new32(left: i32, right: i32) -> Results;
 }
 
+/// Runs the operation `T` with the given width, `None` if the width is not
+/// supported.
+fn dispatch<T: Supported>(left: i32, right: i32, of: i32) -> Option<Results> {
+    match of {
+        4 => Some(T::new4(left, right)),
+        8 => Some(T::new8(left, right)),
+        16 => Some(T::new16(left, right)),
+        32 => Some(T::new32(left, right)),
+        _ => None,
+    }
+}
+
 macro_rules! runner {
-    ($name:ident, $fun:ident) => {
-        #[wasm_bindgen]
+    ($name:ident, $js_name:ident, $fun:ident) => {
         pub fn $name(left: i32, right: i32, of: i32) -> Result<Results, JsValue> {
-            match of {
-                4 => Ok($fun::new4(left, right)),
-                8 => Ok($fun::new8(left, right)),
-                16 => Ok($fun::new16(left, right)),
-                32 => Ok($fun::new32(left, right)),
-                _ => Err(JsValue::from("unsupported value")),
-            }
+            dispatch::<$fun>(left, right, of).ok_or_else(|| JsValue::from("unsupported value"))
+        }
+
+        /// the binary and hex digits of the value are written with `options`
+        /// if given, see [`Results::with_options`].
+        #[wasm_bindgen(js_name = $name)]
+        pub fn $js_name(
+            left: i32,
+            right: i32,
+            of: i32,
+            options: Option<FormatOptions>,
+        ) -> Result<Results, JsValue> {
+            Ok($name(left, right, of)?.with_options(&options.unwrap_or_default()))
         }
     };
 }
 
-runner!(add, Add);
-runner!(sub, Sub);
-runner!(and, And);
-runner!(nand, Nand);
-runner!(or, Or);
-runner!(xor, Xor);
+runner!(add, add_js, Add);
+runner!(sub, sub_js, Sub);
+runner!(and, and_js, And);
+runner!(nand, nand_js, Nand);
+runner!(or, or_js, Or);
+runner!(xor, xor_js, Xor);
+runner!(nor, nor_js, Nor);
+runner!(xnor, xnor_js, Xnor);
+runner!(orn, orn_js, Orn);
+runner!(imply, imply_js, Imply);
+
+/// The names [`operation`] and [`BatchResults::run`] know.
+pub(crate) const OPERATIONS: [&str; 10] = [
+    "add", "sub", "and", "nand", "or", "xor", "nor", "xnor", "orn", "imply",
+];
+
+/// Runs the operation called `name` with the width `of`, for the native
+/// tools which can not go through the `JsValue` errors.
+pub fn operation(name: &str, left: i32, right: i32, of: i32) -> Result<Results, String> {
+    let results = match name {
+        "add" => dispatch::<Add>(left, right, of),
+        "sub" => dispatch::<Sub>(left, right, of),
+        "and" => dispatch::<And>(left, right, of),
+        "nand" => dispatch::<Nand>(left, right, of),
+        "or" => dispatch::<Or>(left, right, of),
+        "xor" => dispatch::<Xor>(left, right, of),
+        "nor" => dispatch::<Nor>(left, right, of),
+        "xnor" => dispatch::<Xnor>(left, right, of),
+        "orn" => dispatch::<Orn>(left, right, of),
+        "imply" => dispatch::<Imply>(left, right, of),
+        _ => return Err(format!("unknown operation '{}'", name)),
+    };
+    results.ok_or_else(|| "unsupported value".to_string())
+}
@@ -0,0 +1,332 @@
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::api::FormattedValue;
+
+/// Everything that can go wrong while reading a literal, positions are byte
+/// offsets into the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnsupportedWidth(i32),
+    InvalidDigit {
+        at: usize,
+        found: char,
+        radix: u32,
+    },
+    MissingDigits {
+        at: usize,
+    },
+    InvalidChar {
+        at: usize,
+    },
+    InvalidSize {
+        at: usize,
+    },
+    /// the base letter of a sized literal, like the `h` of `8'hFF`.
+    InvalidBase {
+        at: usize,
+        found: char,
+    },
+    TooLarge,
+    /// the digits of a sized literal do not fit into its size.
+    SizeOverflow {
+        size: u32,
+    },
+    /// the size of a sized literal is larger than the chosen width.
+    SizeMismatch {
+        size: u32,
+        of: i32,
+    },
+    /// the value fits neither the signed nor the unsigned range.
+    OutOfRange {
+        value: i128,
+        of: i32,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the literal is empty"),
+            Self::UnsupportedWidth(of) => write!(f, "unsupported width {}", of),
+            Self::InvalidDigit { at, found, radix } => {
+                write!(f, "invalid digit '{}' for base {} at {}", found, radix, at)
+            }
+            Self::MissingDigits { at } => write!(f, "expected digits at {}", at),
+            Self::InvalidChar { at } => write!(f, "invalid character literal at {}", at),
+            Self::InvalidSize { at } => write!(f, "invalid size at {}", at),
+            Self::InvalidBase { at, found } => write!(f, "invalid base '{}' at {}", found, at),
+            Self::TooLarge => write!(f, "the literal is too large"),
+            Self::SizeOverflow { size } => {
+                write!(f, "the digits do not fit into {} bits", size)
+            }
+            Self::SizeMismatch { size, of } => {
+                write!(f, "a {} bit literal does not fit into {} bits", size, of)
+            }
+            Self::OutOfRange { value, of } => {
+                let min = -(1i128 << (of - 1));
+                let max = (1i128 << of) - 1;
+                write!(
+                    f,
+                    "{} is out of range for {} bits ({}..={})",
+                    value, of, min, max
+                )
+            }
+        }
+    }
+}
+
+impl From<ParseError> for JsValue {
+    fn from(err: ParseError) -> Self {
+        JsValue::from(err.to_string())
+    }
+}
+
+/// A literal read from the user, validated against a width.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Literal {
+    /// the bit pattern, only the lower `of` bits are set.
+    pub value: i32,
+    pub of: i32,
+    /// the value is within `-2^(of-1)..2^(of-1)`.
+    pub fits_signed: bool,
+    /// the value is within `0..2^of`.
+    pub fits_unsigned: bool,
+    size: Option<u32>,
+}
+
+#[wasm_bindgen]
+impl Literal {
+    #[wasm_bindgen(getter)]
+    pub fn get_formatted(&self) -> FormattedValue {
+        FormattedValue::of(self.value, self.of).expect("the width was checked before")
+    }
+
+    /// the width written in the literal, like the 8 of `8'hFF` or `x"FF"`.
+    #[wasm_bindgen(getter)]
+    pub fn get_size(&self) -> Option<u32> {
+        self.size
+    }
+}
+
+impl Literal {
+    /// Reads `input` for the width `of`. Without a prefix the digits are read
+    /// in `radix`.
+    ///
+    /// Supported are `0b1010`, `0o17`, `0x_FF_FF`, `-5`, `'A'`, the Verilog
+    /// literals `8'hFF` or `8'shF3` and the VHDL literals `x"3F"`, `o"17"`
+    /// and `b"1011"`, `_` may be used as separator.
+    ///
+    /// The size of a sized literal may be smaller than `of`, the value is
+    /// then zero or sign extended.
+    pub fn parse(input: &str, radix: u32, of: i32) -> Result<Self, ParseError> {
+        Self::parse_sized(input, radix, of, false)
+    }
+
+    /// Like [`Literal::parse`], but with `overrides` the size written in the
+    /// literal is used as width instead of `of`.
+    pub fn parse_sized(
+        input: &str,
+        radix: u32,
+        of: i32,
+        overrides: bool,
+    ) -> Result<Self, ParseError> {
+        let (value, size) = read(input, radix)?;
+        let of = match size {
+            Some(size) if overrides => size as i32,
+            _ => of,
+        };
+
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return Err(ParseError::UnsupportedWidth(of));
+        }
+        if let Some(size) = size {
+            if size > of as u32 {
+                return Err(ParseError::SizeMismatch { size, of });
+            }
+        }
+
+        let fits_signed = -(1i128 << (of - 1)) <= value && value < 1i128 << (of - 1);
+        let fits_unsigned = 0 <= value && value < 1i128 << of;
+        if !fits_signed && !fits_unsigned {
+            return Err(ParseError::OutOfRange { value, of });
+        }
+
+        let mask = (1i128 << of) - 1;
+        Ok(Self {
+            value: (value & mask) as u32 as i32,
+            of,
+            fits_signed,
+            fits_unsigned,
+            size,
+        })
+    }
+}
+
+/// Reads a literal, see [`Literal::parse`].
+#[wasm_bindgen]
+pub fn parse(input: &str, radix: u32, of: i32) -> Result<Literal, JsValue> {
+    Ok(Literal::parse(input, radix, of)?)
+}
+
+/// reads the mathematical value of the literal and the size, if it has one.
+fn read(input: &str, radix: u32) -> Result<(i128, Option<u32>), ParseError> {
+    let start = input.len() - input.trim_start().len();
+    let text = input.trim();
+    if text.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let (negative, at) = match text.as_bytes()[0] {
+        b'-' => (true, start + 1),
+        b'+' => (false, start + 1),
+        _ => (false, start),
+    };
+    let rest = &input[at..start + text.len()];
+
+    let (value, size) = if rest.starts_with('\'') {
+        (character(rest, at)?, None)
+    } else if rest.ends_with('"') {
+        let (value, size) = bit_string(rest, at)?;
+        (value, Some(size))
+    } else if let Some(quote) = rest.find('\'') {
+        let (value, size) = sized(rest, quote, at)?;
+        (value, Some(size))
+    } else {
+        // a prefix letter which is a digit of `radix` is read as digit, so
+        // `0B1` in hex is 0xB1
+        let prefix = rest
+            .get(1..2)
+            .filter(|letter| rest.starts_with('0') && !letter.chars().all(|c| c.is_digit(radix)));
+        let (digits, radix, offset) = match prefix {
+            Some("x") | Some("X") => (&rest[2..], 16, 2),
+            Some("o") | Some("O") => (&rest[2..], 8, 2),
+            Some("b") | Some("B") => (&rest[2..], 2, 2),
+            _ => (rest, radix, 0),
+        };
+        (digits_of(digits, radix, at + offset)?, None)
+    };
+
+    Ok((if negative { -value } else { value }, size))
+}
+
+/// reads `digits` in `radix`, `_` is skipped.
+fn digits_of(digits: &str, radix: u32, at: usize) -> Result<i128, ParseError> {
+    let mut value: i128 = 0;
+    let mut any = false;
+
+    for (i, c) in digits.char_indices() {
+        if c == '_' {
+            continue;
+        }
+        let digit = c.to_digit(radix).ok_or(ParseError::InvalidDigit {
+            at: at + i,
+            found: c,
+            radix,
+        })?;
+        value = value
+            .checked_mul(radix as i128)
+            .and_then(|v| v.checked_add(digit as i128))
+            .filter(|v| *v <= u64::MAX as i128)
+            .ok_or(ParseError::TooLarge)?;
+        any = true;
+    }
+
+    if !any {
+        return Err(ParseError::MissingDigits { at });
+    }
+    Ok(value)
+}
+
+/// reads a sized literal like `8'hFF` or `4'sb1011`, the quote is at `quote`.
+fn sized(text: &str, quote: usize, at: usize) -> Result<(i128, u32), ParseError> {
+    let size = text[..quote]
+        .parse::<u32>()
+        .ok()
+        .filter(|size| (1..=32).contains(size))
+        .ok_or(ParseError::InvalidSize { at })?;
+
+    let mut pos = quote + 1;
+    let signed = matches!(text[pos..].chars().next(), Some('s') | Some('S'));
+    if signed {
+        pos += 1;
+    }
+
+    let radix = match text[pos..].chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some('b') => 2,
+        Some('o') => 8,
+        Some('d') => 10,
+        Some('h') => 16,
+        Some(found) => {
+            return Err(ParseError::InvalidBase {
+                at: at + pos,
+                found,
+            })
+        }
+        None => return Err(ParseError::MissingDigits { at: at + pos }),
+    };
+    pos += 1;
+
+    let value = digits_of(&text[pos..], radix, at + pos)?;
+    if value >= 1i128 << size {
+        return Err(ParseError::SizeOverflow { size });
+    }
+
+    let value = if signed && value >> (size - 1) & 1 == 1 {
+        value - (1i128 << size)
+    } else {
+        value
+    };
+    Ok((value, size))
+}
+
+/// reads a VHDL bit string like `x"3F"`, `o"17"`, `b"1011"` or `"1011"`,
+/// every digit adds its bits to the size.
+fn bit_string(text: &str, at: usize) -> Result<(i128, u32), ParseError> {
+    let open = text.find('"').expect("the text ends with a quote");
+    let (radix, bits) = match &text[..open] {
+        "" | "b" | "B" => (2, 1),
+        "o" | "O" => (8, 3),
+        "x" | "X" => (16, 4),
+        prefix => {
+            let found = prefix.chars().next().expect("the prefix is not empty");
+            return Err(ParseError::InvalidBase { at, found });
+        }
+    };
+
+    let digits = text
+        .get(open + 1..text.len() - 1)
+        .ok_or(ParseError::MissingDigits { at: at + open })?;
+    let value = digits_of(digits, radix, at + open + 1)?;
+    let size = digits.chars().filter(|c| *c != '_').count() as u32 * bits;
+
+    Ok((value, size))
+}
+
+/// reads a character literal like `'A'` or `'\n'`.
+fn character(text: &str, at: usize) -> Result<i128, ParseError> {
+    let inner = text
+        .strip_prefix('\'')
+        .and_then(|t| t.strip_suffix('\''))
+        .ok_or(ParseError::InvalidChar { at })?;
+
+    let mut chars = inner.chars();
+    let c = match (chars.next(), chars.next(), chars.next()) {
+        (Some('\\'), Some(escaped), None) => match escaped {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' | '\'' => escaped,
+            _ => return Err(ParseError::InvalidChar { at }),
+        },
+        (Some(c), None, None) => c,
+        _ => return Err(ParseError::InvalidChar { at }),
+    };
+
+    Ok(c as i128)
+}
@@ -1,5 +1,7 @@
 use std::ops::{BitAnd, BitOr, BitXor, Not};
 
+use wasm_bindgen::prelude::*;
+
 use crate::{
     api::{ResultFlags, ResultValue},
     utils, Results, Supported,
@@ -93,3 +95,102 @@ functs!(Or | left, right | { left | right });
 functs!(Xor | left, right | { left ^ right });
 
 functs!(Nand | left, right | { !(left & right) });
+
+functs!(Nor | left, right | { !(left | right) });
+
+functs!(Xnor | left, right | { !(left ^ right) });
+
+functs!(Orn | left, right | { left | !right });
+
+functs!(Imply | left, right | { !left | right });
+
+/// All 16 two-input boolean functions, indexed by their truth table.
+///
+/// Bit `(a << 1) | b` of the table holds the output for the inputs `a` and
+/// `b`, so reading the table from the most significant bit gives
+/// `f(1,1) f(1,0) f(0,1) f(0,0)`, e.g. AND is `0b1000`.
+const FUNCTIONS: [(&str, u8); 18] = [
+    ("false", 0b0000),
+    ("nor", 0b0001),
+    ("cnimply", 0b0010),
+    ("not_a", 0b0011),
+    ("nimply", 0b0100),
+    ("bic", 0b0100),
+    ("not_b", 0b0101),
+    ("xor", 0b0110),
+    ("nand", 0b0111),
+    ("and", 0b1000),
+    ("xnor", 0b1001),
+    ("b", 0b1010),
+    ("imply", 0b1011),
+    ("a", 0b1100),
+    ("cimply", 0b1101),
+    ("orn", 0b1101),
+    ("or", 0b1110),
+    ("true", 0b1111),
+];
+
+/// the truth table of the function called `name`, case is ignored.
+pub fn table_of(name: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    FUNCTIONS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, table)| *table)
+}
+
+/// the canonical name of the function with the given truth table.
+pub fn name_of(table: u8) -> &'static str {
+    FUNCTIONS
+        .iter()
+        .find(|(_, t)| *t == table & 0xF)
+        .map(|(n, _)| *n)
+        .expect("all 16 tables are named")
+}
+
+/// applies the truth table to every bit pair of `left` and `right`.
+pub(crate) fn run_table(table: u8, left: u32, right: u32) -> u32 {
+    let minterms = [!left & !right, !left & right, left & !right, left & right];
+
+    minterms
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| table >> i & 1 == 1)
+        .fold(0, |acc, (_, minterm)| acc | minterm)
+}
+
+/// Runs the boolean function given by its 4-bit truth table, see
+/// [`FUNCTIONS`] for the encoding.
+#[wasm_bindgen]
+pub fn logic(left: i32, right: i32, table: u8, of: i32) -> Result<Results, JsValue> {
+    if table > 0xF {
+        return Err(JsValue::from("the truth table has only 4 bits"));
+    }
+    let raw = run_table(table, left as u32, right as u32);
+    Results::from_raw(raw, of).ok_or_else(|| JsValue::from("unsupported value"))
+}
+
+/// Runs the boolean function called `name`, like `xnor` or `imply`.
+#[wasm_bindgen]
+pub fn logic_by_name(left: i32, right: i32, name: &str, of: i32) -> Result<Results, JsValue> {
+    let table = table_of(name).ok_or_else(|| JsValue::from("unknown boolean function"))?;
+    logic(left, right, table, of)
+}
+
+/// The truth table of every bit, one line per bit starting at the most
+/// significant one, in the form `bit | a b | f`.
+#[wasm_bindgen]
+pub fn logic_table(left: i32, right: i32, table: u8, of: i32) -> Result<String, JsValue> {
+    let res = logic(left, right, table, of)?;
+    let raw = res.get_value().get_raw();
+
+    let mut out = format!("{} (table {:04b})\nbit | a b | f\n", name_of(table), table);
+    for bit in (0..of as u32).rev() {
+        let a = left as u32 >> bit & 1;
+        let b = right as u32 >> bit & 1;
+        let f = raw >> bit & 1;
+        out.push_str(&format!("{:>3} | {} {} | {}\n", bit, a, b, f));
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,91 @@
+use wasm_bindgen::prelude::*;
+
+use crate::api::FormattedValue;
+
+/// the bytes of the lower `of` bits of `raw` in memory order, `None` for
+/// the widths below 16 bits.
+pub fn bytes(raw: u32, of: usize, little_endian: bool) -> Option<Vec<u8>> {
+    if of < 16 {
+        return None;
+    }
+    let count = of / 8;
+    let bytes = if little_endian {
+        raw.to_le_bytes()[..count].to_vec()
+    } else {
+        raw.to_be_bytes()[4 - count..].to_vec()
+    };
+    Some(bytes)
+}
+
+/// the bytes as they sit at consecutive addresses, like `+0: 78  +1: 56`.
+pub fn layout(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(address, byte)| format!("+{}: {:02X}", address, byte))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// reads a memory dump like `78 56 34 12`, `0x78,0x56` or `7856`.
+fn read(dump: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+
+    for token in dump.split(|c: char| c.is_whitespace() || c == ',') {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if digits.is_empty() {
+            continue;
+        }
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid byte '{}'", token));
+        }
+
+        // a run of digits like `7856` holds a byte per digit pair
+        let digits = if digits.len() % 2 == 1 {
+            format!("0{}", digits)
+        } else {
+            digits.to_string()
+        };
+        for pair in digits.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).expect("the digits are ascii");
+            bytes.push(u8::from_str_radix(pair, 16).expect("the digits were checked"));
+        }
+    }
+
+    Ok(bytes)
+}
+
+impl FormattedValue {
+    /// builds the value of `of / 8` bytes read from memory, the first byte
+    /// is at the lowest address.
+    pub fn from_memory(dump: &str, little_endian: bool, of: i32) -> Result<Self, String> {
+        if !matches!(of, 16 | 32) {
+            return Err("the memory view needs 16 or 32 bits".to_string());
+        }
+        let bytes = read(dump)?;
+        let count = of as usize / 8;
+        if bytes.len() != count {
+            return Err(format!("expected {} bytes, got {}", count, bytes.len()));
+        }
+
+        let mut word = [0u8; 4];
+        let raw = if little_endian {
+            word[..count].copy_from_slice(&bytes);
+            u32::from_le_bytes(word)
+        } else {
+            word[4 - count..].copy_from_slice(&bytes);
+            u32::from_be_bytes(word)
+        };
+
+        Ok(Self::of(raw as i32, of).expect("the width was checked before"))
+    }
+}
+
+/// Reads a memory dump, see [`FormattedValue::from_memory`].
+#[wasm_bindgen]
+pub fn from_memory(dump: &str, little_endian: bool, of: i32) -> Result<FormattedValue, JsValue> {
+    FormattedValue::from_memory(dump, little_endian, of).map_err(JsValue::from)
+}
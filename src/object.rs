@@ -0,0 +1,93 @@
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    api::{ResultFlags, Results, SIGNED_CONDITIONS, UNSIGNED_CONDITIONS},
+    operation,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &'static str = r#"
+export interface CalcFlags {
+    zero: boolean;
+    negative: boolean;
+    overflow: boolean;
+    carry: boolean;
+    borrow: boolean;
+}
+
+export type CalcCondition =
+    | "EQ" | "NE" | "HS" | "LO" | "HI" | "LS"
+    | "MI" | "PL" | "VS" | "VC" | "GE" | "LT" | "GT" | "LE";
+
+export interface CalcResult {
+    /** the bit pattern, zero extended to 32 bit */
+    raw: number;
+    signed: number;
+    unsigned: number;
+    bin: string;
+    hex: string;
+    flags: CalcFlags;
+    conditions: Record<CalcCondition, boolean>;
+}
+"#;
+
+fn set(target: &Object, key: &str, value: impl Into<JsValue>) {
+    Reflect::set(target, &JsValue::from(key), &value.into())
+        .expect("setting a property of a plain object does not fail");
+}
+
+fn flags(flags: &ResultFlags) -> Object {
+    let object = Object::new();
+    set(&object, "zero", flags.zero);
+    set(&object, "negative", flags.negative);
+    set(&object, "overflow", flags.overflow);
+    set(&object, "carry", flags.carry);
+    set(&object, "borrow", flags.borrow);
+    object
+}
+
+fn conditions(flags: &ResultFlags) -> Object {
+    let object = Object::new();
+    for (code, _) in UNSIGNED_CONDITIONS.iter().chain(SIGNED_CONDITIONS.iter()) {
+        set(&object, code, flags.condition(code) == Some(true));
+    }
+    object
+}
+
+#[wasm_bindgen]
+impl Results {
+    /// everything in one plain object, see `CalcResult` in the TypeScript
+    /// definitions.
+    #[wasm_bindgen(unchecked_return_type = "CalcResult")]
+    pub fn to_object(&self) -> JsValue {
+        let value = self.get_value();
+        let raw = value.get_raw();
+        let of = value.get_bin().len() as u32;
+        let signed = if of < 32 && raw >> (of - 1) & 1 == 1 {
+            raw as i64 - (1i64 << of)
+        } else {
+            raw as i32 as i64
+        };
+
+        let object = Object::new();
+        set(&object, "raw", raw);
+        set(&object, "signed", signed as f64);
+        set(&object, "unsigned", raw);
+        set(&object, "bin", value.get_bin());
+        set(&object, "hex", value.get_hex());
+
+        let result_flags = self.get_flags();
+        set(&object, "flags", flags(&result_flags));
+        set(&object, "conditions", conditions(&result_flags));
+        object.into()
+    }
+}
+
+/// Runs the operation called `op` (`add`, `sub`, `and`, ...) and returns a
+/// single plain object instead of the getters of [`Results`].
+#[wasm_bindgen(unchecked_return_type = "CalcResult")]
+pub fn calculate(op: &str, left: i32, right: i32, of: i32) -> Result<JsValue, JsValue> {
+    let results = operation(op, left, right, of)?;
+    Ok(results.to_object())
+}
@@ -0,0 +1,351 @@
+use crate::{
+    addition::{add_with_carry, Add, Sub},
+    api::{FormattedValue, ResultFlags, Results},
+    dispatch,
+    expression::Evaluation,
+    literal::Literal,
+    logical::{And, Or, Xor},
+};
+
+const HELP: &str = "r0 = 0x7F            set a register, any expression works
+adds r0, r0, #1      run an instruction, `#` marks an immediate
+adcs r1, r1, #0      ... with the carry of the previous instruction
+0x7F + r0            evaluate an expression, the value is kept as `_`
+flags | regs         show the flags or the registers
+width 16             change the width
+history | !3 | !!    show the history or run a line of it again
+
+instructions: add adc sub sbc and orr eor bic mov mvn, with an `s` suffix
+the flags are updated, cmp cmn and tst always update them";
+
+/// An interactive session, like a tiny Cortex-M0 with 16 registers and the
+/// flags carried from one instruction to the next.
+#[derive(Debug, Clone)]
+pub struct Session {
+    of: i32,
+    registers: [u32; 16],
+    /// the last value, available as `_`.
+    last: u32,
+    flags: ResultFlags,
+    history: Vec<String>,
+}
+
+impl Session {
+    /// a session with all registers and flags cleared, `None` if the width
+    /// is not supported.
+    pub fn new(of: i32) -> Option<Self> {
+        if !matches!(of, 4 | 8 | 16 | 32) {
+            return None;
+        }
+        Some(Self {
+            of,
+            registers: [0; 16],
+            last: 0,
+            flags: ResultFlags::new(false, false, false, false),
+            history: Vec::new(),
+        })
+    }
+
+    pub fn of(&self) -> i32 {
+        self.of
+    }
+
+    pub fn register(&self, index: usize) -> Option<u32> {
+        self.registers.get(index).copied()
+    }
+
+    pub fn flags(&self) -> &ResultFlags {
+        &self.flags
+    }
+
+    /// every line run so far, without the `history` and `!` commands.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// adds lines of an earlier session, so they can be run with `!`.
+    pub fn extend_history(&mut self, lines: impl IntoIterator<Item = String>) {
+        self.history.extend(lines);
+    }
+
+    /// runs a single line and returns what to show.
+    pub fn run(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+
+        let line = match line {
+            "" => return Ok(String::new()),
+            "help" => return Ok(HELP.to_string()),
+            "history" => return Ok(self.show_history()),
+            "!!" => self.history.last().cloned().ok_or("the history is empty")?,
+            _ if line.starts_with('!') => {
+                let index = line[1..]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| index.checked_sub(1))
+                    .and_then(|index| self.history.get(index))
+                    .ok_or_else(|| format!("no history entry '{}'", &line[1..]))?;
+                index.clone()
+            }
+            _ => line.to_string(),
+        };
+
+        self.history.push(line.clone());
+        self.execute(&line)
+    }
+
+    fn execute(&mut self, line: &str) -> Result<String, String> {
+        let mut words = line.splitn(2, char::is_whitespace);
+        let command = words.next().unwrap_or_default().to_lowercase();
+        let rest = words.next().unwrap_or_default().trim();
+
+        match command.as_str() {
+            "flags" => return Ok(self.show_flags()),
+            "regs" => return Ok(self.show_registers()),
+            "width" => return self.set_width(rest),
+            _ => {}
+        }
+
+        if let Some((target, expr)) = line.split_once('=') {
+            let index = register(target.trim())
+                .ok_or_else(|| format!("'{}' is no register", target.trim()))?;
+            let value = self.evaluate(expr)?;
+            self.registers[index] = value;
+            self.last = value;
+            return Ok(format!("r{} = {}", index, self.show_value(value)));
+        }
+
+        if let Some(instruction) = Instruction::parse(&command) {
+            return self.instruction(instruction, rest);
+        }
+
+        let value = self.evaluate(line).map_err(|err| {
+            if command.chars().all(char::is_alphabetic) && register(&command).is_none() {
+                format!("unknown command '{}'", command)
+            } else {
+                err
+            }
+        })?;
+        self.last = value;
+        Ok(format!("_ = {}", self.show_value(value)))
+    }
+
+    /// evaluates an expression, registers and `_` are replaced by their
+    /// values first.
+    fn evaluate(&self, expr: &str) -> Result<u32, String> {
+        let mut replaced = String::new();
+        let mut word = String::new();
+        let mut quoted = false;
+
+        for c in expr.chars().chain([' ']) {
+            if quoted || c == '\'' && word.is_empty() {
+                // character literals are kept as they are
+                quoted = !(quoted && c == '\'' && !word.ends_with('\\'));
+                word.push(c);
+                if !quoted {
+                    replaced.push_str(&word);
+                    word.clear();
+                }
+            } else if c.is_ascii_alphanumeric() || c == '_' || c == '\'' {
+                word.push(c);
+            } else {
+                replaced.push_str(&self.substitute(&word));
+                word.clear();
+                replaced.push(c);
+            }
+        }
+
+        let evaluation = Evaluation::run(&replaced, self.of, false)?;
+        Ok(evaluation.get_results().get_value().get_raw())
+    }
+
+    fn substitute(&self, word: &str) -> String {
+        let value = match word {
+            "_" => self.last,
+            _ => match register(word) {
+                Some(index) => self.registers[index],
+                None => return word.to_string(),
+            },
+        };
+        format!("0x{:X}", value)
+    }
+
+    /// an operand of an instruction, a register, `_` or `#imm`.
+    fn operand(&self, text: &str) -> Result<u32, String> {
+        let text = text.trim();
+        if let Some(immediate) = text.strip_prefix('#') {
+            let literal = Literal::parse(immediate, 10, self.of).map_err(|err| err.to_string())?;
+            return Ok(literal.value as u32);
+        }
+        match text {
+            "_" => Ok(self.last),
+            _ => register(text)
+                .map(|index| self.registers[index])
+                .ok_or_else(|| format!("'{}' is no register or immediate", text)),
+        }
+    }
+
+    fn instruction(&mut self, instruction: Instruction, operands: &str) -> Result<String, String> {
+        let operands: Vec<&str> = operands.split(',').map(str::trim).collect();
+        let Instruction { op, set_flags } = instruction;
+
+        // `adds r0, #1` is short for `adds r0, r0, #1`, `mov` and the
+        // comparisons only take two operands
+        let (target, left, right) = match (op, &operands[..]) {
+            ("mov" | "mvn", [rd, op2]) => (Some(*rd), None, *op2),
+            ("cmp" | "cmn" | "tst", [rn, op2]) => (None, Some(*rn), *op2),
+            ("mov" | "mvn" | "cmp" | "cmn" | "tst", _) => {
+                return Err(format!("{} takes two operands", op))
+            }
+            (_, [rd, op2]) => (Some(*rd), Some(*rd), *op2),
+            (_, [rd, rn, op2]) => (Some(*rd), Some(*rn), *op2),
+            _ => return Err(format!("{} takes two or three operands", op)),
+        };
+
+        let target = match target {
+            Some(rd) => Some(register(rd).ok_or_else(|| format!("'{}' is no register", rd))?),
+            None => None,
+        };
+        let left = match left {
+            Some(rn) => self.operand(rn)?,
+            None => 0,
+        };
+        let right = self.operand(right)?;
+
+        let results = self.compute(op, left, right);
+        let value = results.get_value().get_raw();
+
+        let mut out = match target {
+            Some(index) => {
+                self.registers[index] = value;
+                format!("r{} = {}", index, self.show_value(value))
+            }
+            None => format!("{} {}", op, self.show_value(value)),
+        };
+        self.last = value;
+
+        if set_flags {
+            let flags = results.get_flags();
+            self.flags = if matches!(op, "add" | "adc" | "sub" | "sbc" | "cmp" | "cmn") {
+                flags
+            } else {
+                // the logical instructions keep C and V without a shift
+                ResultFlags::new(
+                    flags.zero,
+                    flags.negative,
+                    self.flags.overflow,
+                    self.flags.carry,
+                )
+            };
+            out.push_str(&format!("  {}", self.show_flags()));
+        }
+
+        Ok(out)
+    }
+
+    fn compute(&self, op: &str, left: u32, right: u32) -> Results {
+        let of = self.of;
+        let carry = self.flags.carry;
+        let (left_i, right_i) = (left as i32, right as i32);
+
+        let results = match op {
+            "add" | "cmn" => dispatch::<Add>(left_i, right_i, of),
+            "adc" => add_with_carry(left, right, carry, of),
+            "sub" | "cmp" => dispatch::<Sub>(left_i, right_i, of),
+            "sbc" => add_with_carry(left, !right, carry, of),
+            "and" | "tst" => dispatch::<And>(left_i, right_i, of),
+            "orr" => dispatch::<Or>(left_i, right_i, of),
+            "eor" => dispatch::<Xor>(left_i, right_i, of),
+            "bic" => Results::from_raw(left & !right, of),
+            "mov" => Results::from_raw(right, of),
+            "mvn" => Results::from_raw(!right, of),
+            _ => unreachable!("the instruction was parsed before"),
+        };
+        results.expect("the width was checked before")
+    }
+
+    fn set_width(&mut self, width: &str) -> Result<String, String> {
+        if width.is_empty() {
+            return Ok(format!("width {}", self.of));
+        }
+        let of = width
+            .parse::<i32>()
+            .ok()
+            .filter(|of| matches!(of, 4 | 8 | 16 | 32))
+            .ok_or_else(|| format!("unsupported width '{}'", width))?;
+
+        // the registers keep their lower bits
+        let mask = if of == 32 { u32::MAX } else { (1 << of) - 1 };
+        for register in self.registers.iter_mut() {
+            *register &= mask;
+        }
+        self.last &= mask;
+        self.of = of;
+
+        Ok(format!("width {}", of))
+    }
+
+    fn show_value(&self, value: u32) -> String {
+        let value = FormattedValue::of(value as i32, self.of).expect("the width was checked");
+        format!(
+            "0x{} ({}, {})  {}",
+            value.get_hex(),
+            value.get_unsigned(),
+            value.get_signed(),
+            value.get_bin()
+        )
+    }
+
+    fn show_flags(&self) -> String {
+        let flags = &self.flags;
+        format!(
+            "N={} Z={} C={} V={}",
+            flags.negative as u8, flags.zero as u8, flags.carry as u8, flags.overflow as u8
+        )
+    }
+
+    fn show_registers(&self) -> String {
+        self.registers
+            .iter()
+            .enumerate()
+            .map(|(index, value)| format!("r{:<2} = {}", index, self.show_value(*value)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn show_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(index, line)| format!("{:4}  {}", index + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// the index of a register like `r0` or `R12`.
+fn register(name: &str) -> Option<usize> {
+    let index = name.strip_prefix(['r', 'R'])?.parse::<usize>().ok()?;
+    Some(index).filter(|index| *index < 16 && !name[1..].starts_with('+'))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Instruction {
+    op: &'static str,
+    set_flags: bool,
+}
+
+impl Instruction {
+    const OPS: [&'static str; 13] = [
+        "add", "adc", "sub", "sbc", "and", "orr", "eor", "bic", "mov", "mvn", "cmp", "cmn", "tst",
+    ];
+
+    fn parse(mnemonic: &str) -> Option<Self> {
+        let (op, set_flags) = match mnemonic.strip_suffix('s') {
+            Some(op) if Self::OPS.contains(&op) => (op, true),
+            _ => (mnemonic, false),
+        };
+        let op = *Self::OPS.iter().find(|known| **known == op)?;
+        let set_flags = set_flags || matches!(op, "cmp" | "cmn" | "tst");
+        Some(Self { op, set_flags })
+    }
+}
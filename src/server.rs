@@ -0,0 +1,176 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{
+    api::{FormattedValue, ResultFlags, ResultValue, SIGNED_CONDITIONS, UNSIGNED_CONDITIONS},
+    literal::Literal,
+    operation,
+};
+
+/// An error answered instead of results, `code` is meant for programs and
+/// `message` for humans.
+#[derive(Serialize)]
+struct Error {
+    code: &'static str,
+    message: String,
+}
+
+impl Error {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Reply {
+    Answer {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<Value>,
+        op: String,
+        width: i32,
+        #[serde(flatten)]
+        output: Output,
+    },
+    Failure {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<Value>,
+        error: Error,
+    },
+    Batch(Vec<Reply>),
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Output {
+    Format {
+        value: FormattedValue,
+    },
+    Operation {
+        value: ResultValue,
+        flags: ResultFlags,
+        conditions: Conditions,
+    },
+}
+
+/// Answers one line of the line-delimited JSON server.
+///
+/// A request looks like `{"op":"add","left":5,"right":3,"width":8}`, the
+/// operands may also be literals like `"0x7F"`, `format` takes a single
+/// `value`. The width defaults to 8 and an `id` is sent back as it is. An
+/// array of requests is answered with an array, in the same order.
+pub fn respond(line: &str) -> String {
+    let reply = match serde_json::from_str::<Value>(line) {
+        Err(err) => failure(None, Error::new("parse_error", err.to_string())),
+        Ok(Value::Array(requests)) if requests.is_empty() => {
+            failure(None, Error::new("invalid_request", "the batch is empty"))
+        }
+        Ok(Value::Array(requests)) => Reply::Batch(requests.iter().map(answer).collect()),
+        Ok(request) => answer(&request),
+    };
+    serde_json::to_string(&reply).expect("the replies only hold strings, numbers and maps")
+}
+
+fn answer(request: &Value) -> Reply {
+    let id = request.get("id").cloned();
+    match run(request) {
+        Ok((op, width, output)) => Reply::Answer {
+            id,
+            op,
+            width,
+            output,
+        },
+        Err(err) => failure(id, err),
+    }
+}
+
+fn failure(id: Option<Value>, error: Error) -> Reply {
+    Reply::Failure { id, error }
+}
+
+fn run(request: &Value) -> Result<(String, i32, Output), Error> {
+    if !request.is_object() {
+        return Err(Error::new("invalid_request", "a request is an object"));
+    }
+    let op = match request.get("op") {
+        Some(Value::String(op)) => op.clone(),
+        _ => return Err(Error::new("invalid_request", "missing \"op\"")),
+    };
+
+    let of = match request.get("width") {
+        None => 8,
+        Some(Value::Number(width)) => width
+            .as_i64()
+            .filter(|of| matches!(of, 4 | 8 | 16 | 32))
+            .ok_or_else(|| {
+                Error::new("unsupported_width", format!("unsupported width {}", width))
+            })? as i32,
+        Some(_) => return Err(Error::new("unsupported_width", "the width is a number")),
+    };
+
+    if op == "format" {
+        let value = operand(request, "value", of)?;
+        let output = Output::Format {
+            value: value.get_formatted(),
+        };
+        return Ok((op, of, output));
+    }
+
+    let left = operand(request, "left", of)?;
+    let right = operand(request, "right", of)?;
+    let results =
+        operation(&op, left.value, right.value, of).map_err(|err| Error::new("unknown_op", err))?;
+
+    let flags = results.get_flags();
+    let output = Output::Operation {
+        value: results.get_value(),
+        conditions: Conditions(flags.clone()),
+        flags,
+    };
+    Ok((op, of, output))
+}
+
+fn operand(request: &Value, name: &str, of: i32) -> Result<Literal, Error> {
+    let text = match request.get(name) {
+        Some(Value::Number(number)) => number.to_string(),
+        Some(Value::String(text)) => text.clone(),
+        Some(_) => {
+            return Err(Error::new(
+                "invalid_operand",
+                format!("\"{}\" is a number or a literal", name),
+            ))
+        }
+        None => {
+            return Err(Error::new(
+                "invalid_request",
+                format!("missing \"{}\"", name),
+            ))
+        }
+    };
+    Literal::parse(&text, 10, of)
+        .map_err(|err| Error::new("invalid_operand", format!("\"{}\": {}", name, err)))
+}
+
+/// every condition code once, the unsigned ones first.
+struct Conditions(ResultFlags);
+
+impl Serialize for Conditions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut codes: Vec<&str> = Vec::new();
+        for (code, _) in UNSIGNED_CONDITIONS.iter().chain(SIGNED_CONDITIONS.iter()) {
+            if !codes.contains(code) {
+                codes.push(code);
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(codes.len()))?;
+        for code in codes {
+            let holds = self.0.condition(code).expect("the code is known");
+            map.serialize_entry(code, &holds)?;
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,68 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    addition::Add,
+    api::{LaneResults, ResultValue, Results},
+    Supported,
+};
+
+/// How the GE bits of a lane are derived from its flags.
+#[derive(Clone, Copy)]
+enum Kind {
+    /// GE is set if the signed sum is >= 0, which is the case if N == V.
+    Signed,
+    /// GE is set if the lane produced a carry.
+    Unsigned,
+}
+
+fn ge_of(kind: Kind, res: &Results) -> bool {
+    let flags = res.get_flags();
+    match kind {
+        Kind::Signed => flags.negative == flags.overflow,
+        Kind::Unsigned => flags.carry,
+    }
+}
+
+fn lanes<F>(left: i32, right: i32, size: u32, kind: Kind, fun: F) -> LaneResults
+where
+    F: Fn(i32, i32) -> Results,
+{
+    let count = 32 / size;
+    let mask = (1u64 << size) as u32 - 1;
+    // every lane is reported with GE bits for each of its bytes
+    let ge_per_lane = size / 8;
+    let ge_mask = (1u8 << ge_per_lane) - 1;
+
+    let mut packed = 0u32;
+    let mut ge = 0u8;
+    let mut res = Vec::with_capacity(count as usize);
+
+    for lane in 0..count {
+        let shift = lane * size;
+        let l = ((left as u32 >> shift) & mask) as i32;
+        let r = ((right as u32 >> shift) & mask) as i32;
+
+        let lane_res = fun(l, r);
+        packed |= lane_res.get_value().get_raw() << shift;
+        if ge_of(kind, &lane_res) {
+            ge |= ge_mask << (lane * ge_per_lane);
+        }
+        res.push(lane_res);
+    }
+
+    LaneResults::new(res, ResultValue::new(packed, packed as i32), ge)
+}
+
+macro_rules! lane_op {
+    ($name:ident, $size:expr, $kind:expr, $fun:path) => {
+        #[wasm_bindgen]
+        pub fn $name(left: i32, right: i32) -> LaneResults {
+            lanes(left, right, $size, $kind, $fun)
+        }
+    };
+}
+
+lane_op!(sadd8, 8, Kind::Signed, Add::new8);
+lane_op!(uadd8, 8, Kind::Unsigned, Add::new8);
+lane_op!(sadd16, 16, Kind::Signed, Add::new16);
+lane_op!(uadd16, 16, Kind::Unsigned, Add::new16);
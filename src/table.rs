@@ -0,0 +1,175 @@
+use wasm_bindgen::prelude::*;
+
+use crate::batch::{BatchResults, FLAG_CARRY, FLAG_NEGATIVE, FLAG_OVERFLOW, FLAG_ZERO};
+
+/// How a [`FlagTable`] is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// one line per operand pair, for scripts and spreadsheets.
+    Csv,
+    /// a grid with the left operand as rows and the right one as columns.
+    Markdown,
+    /// the same grid as a `<table>`, for slides.
+    Html,
+}
+
+impl TableFormat {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!("unknown table format '{}'", text)),
+        }
+    }
+}
+
+/// The results and flags of an operation for every pair of operands, only
+/// for 4 and 8 bit, a 16 bit table would have four billion entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagTable {
+    op: String,
+    of: i32,
+    results: BatchResults,
+}
+
+impl FlagTable {
+    pub fn new(op: &str, of: i32) -> Result<Self, String> {
+        if !matches!(of, 4 | 8) {
+            return Err(format!("no table for {} bit, only for 4 and 8", of));
+        }
+        let count = 1u32 << of;
+        let left: Vec<u32> = (0..count * count).map(|i| i / count).collect();
+        let right: Vec<u32> = (0..count * count).map(|i| i % count).collect();
+        Ok(Self {
+            op: op.to_string(),
+            of,
+            results: BatchResults::run(op, &left, &right, of)?,
+        })
+    }
+
+    /// the result and its packed flags, see [`crate::FLAG_NEGATIVE`].
+    pub fn get(&self, left: u32, right: u32) -> Option<(u32, u8)> {
+        let count = 1 << self.of;
+        if left >= count || right >= count {
+            return None;
+        }
+        let index = (left * count + right) as usize;
+        Some((self.results.values()[index], self.results.flags()[index]))
+    }
+
+    pub fn write(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Csv => self.csv(),
+            TableFormat::Markdown => self.markdown(),
+            TableFormat::Html => self.html(),
+        }
+    }
+
+    fn count(&self) -> u32 {
+        1 << self.of
+    }
+
+    fn hex(&self, value: u32) -> String {
+        format!("{:0digits$X}", value, digits = self.of as usize / 4)
+    }
+
+    /// a cell of the grid like `8 N--V`.
+    fn cell(&self, left: u32, right: u32) -> String {
+        let (value, flags) = self.get(left, right).expect("the operands are in range");
+        format!("{} {}", self.hex(value), letters(flags))
+    }
+
+    fn caption(&self) -> String {
+        format!(
+            "{}, {} bit: result NZCV, rows left, columns right",
+            self.op, self.of
+        )
+    }
+
+    fn csv(&self) -> String {
+        let mut lines = vec!["left,right,result,N,Z,C,V".to_string()];
+        for left in 0..self.count() {
+            for right in 0..self.count() {
+                let (value, flags) = self.get(left, right).expect("the operands are in range");
+                let bit = |flag: u8| (flags & flag != 0) as u8;
+                lines.push(format!(
+                    "{},{},{},{},{},{},{}",
+                    self.hex(left),
+                    self.hex(right),
+                    self.hex(value),
+                    bit(FLAG_NEGATIVE),
+                    bit(FLAG_ZERO),
+                    bit(FLAG_CARRY),
+                    bit(FLAG_OVERFLOW)
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn markdown(&self) -> String {
+        let columns: Vec<String> = (0..self.count()).map(|right| self.hex(right)).collect();
+        let mut lines = vec![
+            self.caption(),
+            String::new(),
+            format!("| | {} |", columns.join(" | ")),
+            format!("|---|{}", "---|".repeat(columns.len())),
+        ];
+        for left in 0..self.count() {
+            let cells: Vec<String> = (0..self.count())
+                .map(|right| self.cell(left, right))
+                .collect();
+            lines.push(format!(
+                "| **{}** | {} |",
+                self.hex(left),
+                cells.join(" | ")
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn html(&self) -> String {
+        let mut lines = vec![
+            "<table class=\"flag-table\">".to_string(),
+            format!("  <caption>{}</caption>", self.caption()),
+            "  <thead>".to_string(),
+        ];
+        let columns: String = (0..self.count())
+            .map(|right| format!("<th>{}</th>", self.hex(right)))
+            .collect();
+        lines.push(format!("    <tr><th></th>{}</tr>", columns));
+        lines.push("  </thead>".to_string());
+        lines.push("  <tbody>".to_string());
+        for left in 0..self.count() {
+            let cells: String = (0..self.count())
+                .map(|right| format!("<td>{}</td>", self.cell(left, right)))
+                .collect();
+            lines.push(format!("    <tr><th>{}</th>{}</tr>", self.hex(left), cells));
+        }
+        lines.push("  </tbody>".to_string());
+        lines.push("</table>".to_string());
+        lines.join("\n")
+    }
+}
+
+/// the set flags by letter and the clear ones as `-`, like `N--V`.
+fn letters(flags: u8) -> String {
+    [
+        (FLAG_NEGATIVE, 'N'),
+        (FLAG_ZERO, 'Z'),
+        (FLAG_CARRY, 'C'),
+        (FLAG_OVERFLOW, 'V'),
+    ]
+    .iter()
+    .map(|(flag, letter)| if flags & flag != 0 { *letter } else { '-' })
+    .collect()
+}
+
+/// Writes the table of `op` for every operand pair, `format` is `csv`,
+/// `markdown` or `html`.
+#[wasm_bindgen]
+pub fn flag_table(op: &str, of: i32, format: &str) -> Result<String, JsValue> {
+    let format = TableFormat::parse(format)?;
+    Ok(FlagTable::new(op, of)?.write(format))
+}
@@ -37,3 +37,8 @@ pub fn to_i4(val: u8) -> I4 {
     };
     res as i8
 }
+
+/// the nibble at `index`, counting from the least significant one.
+pub fn nibble(val: u32, index: u32) -> U4 {
+    to_u4((val >> (index * 4)) as u8)
+}
@@ -23,6 +23,14 @@ fn test_human_error() {
     }
 }
 
+#[wasm_bindgen_test]
+fn test_bitfield_human_error() {
+    assert_eq!(true, ct_calculator::ubfx(0, 2, 3, 4).is_err());
+    assert_eq!(true, ct_calculator::bfc(0, 0, 0, 8).is_err());
+    assert_eq!(true, ct_calculator::bfc(0, 0, 1, 64).is_err());
+    assert_eq!(true, ct_calculator::ubfx(0, u32::MAX, 2, 8).is_err());
+}
+
 #[wasm_bindgen_test]
 fn test_i4() {
     for i in 0..=7 {
@@ -128,6 +136,29 @@ fn test_sub_overflow_no_carry() {
     testing_facility_results(&results, left, right, of, ct_calculator::sub);
 }
 
+#[wasm_bindgen_test]
+fn test_sub_zero_carry() {
+    // no borrow is needed, so C is set like SUBS does
+    let left = 0b0101;
+    let right = 0b0000;
+    let of = 4;
+    let flags = ResultFlags::new(false, false, false, true);
+    let values = ResultValue::new4(5, 5);
+    let results = Results::new(flags, values);
+    testing_facility_results(&results, left, right, of, ct_calculator::sub);
+}
+
+#[wasm_bindgen_test]
+fn test_sub_zero_zero_carry() {
+    let left = 0b0000;
+    let right = 0b0000;
+    let of = 4;
+    let flags = ResultFlags::new(true, false, false, true);
+    let values = ResultValue::new4(0, 0);
+    let results = Results::new(flags, values);
+    testing_facility_results(&results, left, right, of, ct_calculator::sub);
+}
+
 #[wasm_bindgen_test]
 fn test_8_one() {
     let left = 0x82;
@@ -218,6 +249,1410 @@ fn test_8_four() {
     }
 }
 
+#[wasm_bindgen_test]
+fn test_lanes_8() {
+    let left = 0x7F01_FF80u32 as i32;
+    let right = 0x0101_0180u32 as i32;
+    {
+        let res = ct_calculator::uadd8(left, right);
+        assert_eq!(0x8002_0000, res.get_value().get_raw());
+        // carries out of byte 0 and 1
+        assert_eq!(0b0011, res.ge);
+        assert_eq!("0011", res.get_ge_bin());
+        assert_eq!(4, res.get_lanes().len());
+    }
+    {
+        let res = ct_calculator::sadd8(left, right);
+        assert_eq!(0x8002_0000, res.get_value().get_raw());
+        // -128 + -128 < 0, -1 + 1 >= 0, 1 + 1 >= 0, 127 + 1 >= 0
+        assert_eq!(0b1110, res.ge);
+        let lanes = res.get_lanes();
+        assert_eq!(true, lanes[0].get_flags().overflow);
+        assert_eq!(true, lanes[3].get_flags().overflow);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_lanes_16() {
+    let left = 0xFFFF_8000u32 as i32;
+    let right = 0x0001_8000u32 as i32;
+    {
+        let res = ct_calculator::uadd16(left, right);
+        assert_eq!(0x0000_0000, res.get_value().get_raw());
+        assert_eq!(0b1111, res.ge);
+    }
+    {
+        let res = ct_calculator::sadd16(left, right);
+        assert_eq!(0b1100, res.ge);
+        assert_eq!("0", res.get_lanes()[0].get_value().get_signed());
+        assert_eq!(true, res.get_lanes()[0].get_flags().overflow);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_bitfield() {
+    {
+        let res = ct_calculator::bfi(0xFF, 0b0010, 2, 3, 8).unwrap();
+        assert_eq!("11101011", res.get_value().get_bin());
+        assert_eq!(Some("111[010]11".to_string()), res.get_field());
+    }
+    {
+        let res = ct_calculator::bfc(0xF, 1, 2, 4).unwrap();
+        assert_eq!("1001", res.get_value().get_bin());
+        assert_eq!(Some("1[00]1".to_string()), res.get_field());
+    }
+    {
+        let res = ct_calculator::ubfx(0xB4, 4, 4, 8).unwrap();
+        assert_eq!("11", res.get_value().get_unsigned());
+        assert_eq!(false, res.get_flags().negative);
+    }
+    {
+        let res = ct_calculator::sbfx(0xB4, 4, 4, 8).unwrap();
+        assert_eq!("-5", res.get_value().get_signed());
+        assert_eq!(Some("1111[1011]".to_string()), res.get_field());
+        assert_eq!(true, res.get_flags().negative);
+    }
+    {
+        let res = ct_calculator::sbfx(-1, 0, 32, 32).unwrap();
+        assert_eq!("-1", res.get_value().get_signed());
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_counting() {
+    let unsigned = |res: Result<Results, JsValue>| res.unwrap().get_value().get_unsigned();
+
+    // zero input at the edges
+    for of in [4, 8, 16, 32].iter() {
+        assert_eq!(of.to_string(), unsigned(ct_calculator::clz(0, *of)));
+        assert_eq!("0", unsigned(ct_calculator::popcount(0, *of)));
+        assert_eq!("0", unsigned(ct_calculator::ffs(0, *of)));
+        assert_eq!("0", unsigned(ct_calculator::fls(0, *of)));
+        assert_eq!("0", unsigned(ct_calculator::rbit(0, *of)));
+    }
+
+    assert_eq!("1", unsigned(ct_calculator::clz(0b0111, 4)));
+    assert_eq!("0", unsigned(ct_calculator::clz(-1, 32)));
+    assert_eq!("4", unsigned(ct_calculator::popcount(-1, 4)));
+    assert_eq!("32", unsigned(ct_calculator::popcount(-1, 32)));
+    assert_eq!("3", unsigned(ct_calculator::ffs(0b0100, 4)));
+    assert_eq!("32", unsigned(ct_calculator::fls(i32::MIN, 32)));
+
+    let res = ct_calculator::rbit(0b0001, 4).unwrap();
+    assert_eq!("1000", res.get_value().get_bin());
+    assert_eq!(true, res.get_flags().negative);
+
+    let res = ct_calculator::rbit(1, 32).unwrap();
+    assert_eq!("80000000", res.get_value().get_hex());
+}
+
+#[wasm_bindgen_test]
+fn test_logic_tables() {
+    let left = 0b1100;
+    let right = 0b1010;
+    // every table applied to the four input combinations gives the table
+    for table in 0..16u8 {
+        let res = ct_calculator::logic(left, right, table, 4).unwrap();
+        assert_eq!(format!("{:04b}", table), res.get_value().get_bin());
+        assert_eq!(table == 0, res.get_flags().zero);
+    }
+
+    let named = [
+        ("and", ct_calculator::and as fn(i32, i32, i32) -> _),
+        ("or", ct_calculator::or),
+        ("xor", ct_calculator::xor),
+        ("nand", ct_calculator::nand),
+        ("nor", ct_calculator::nor),
+        ("xnor", ct_calculator::xnor),
+        ("orn", ct_calculator::orn),
+        ("imply", ct_calculator::imply),
+    ];
+    for (name, fun) in named.iter() {
+        let expected = fun(0x5A, 0x3C, 8).unwrap().get_value();
+        let res = ct_calculator::logic_by_name(0x5A, 0x3C, name, 8).unwrap();
+        assert_eq!(expected.get_bin(), res.get_value().get_bin(), "{}", name);
+    }
+
+    let table = ct_calculator::logic_table(0b10, 0b11, 0b1000, 4).unwrap();
+    assert_eq!(
+        "and (table 1000)\nbit | a b | f\n  3 | 0 0 | 0\n  2 | 0 0 | 0\n  1 | 1 1 | 1\n  0 | 0 1 | 0\n",
+        table
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_evaluate() {
+    {
+        // ((0x7F + 3) & ~0x0F) ^ 5
+        let res = ct_calculator::evaluate("(0x7F + 3) & ~0x0F ^ 5", 8, false).unwrap();
+        assert_eq!("10000101", res.get_results().get_value().get_bin());
+        let ops: Vec<_> = res.get_steps().iter().map(|s| s.get_op()).collect();
+        assert_eq!(vec!["add", "nand", "and", "xor"], ops);
+        // 0x7F + 3 overflows in 8 bit
+        assert_eq!(true, res.get_steps()[0].get_results().get_flags().overflow);
+    }
+    {
+        // 1 + 2 & 3 is (1 + 2) & 3 in both modes, 1 | 2 & 0 is not
+        let c = ct_calculator::evaluate("1 | 2 & 0", 4, false).unwrap();
+        assert_eq!("1", c.get_results().get_value().get_unsigned());
+        let strict = ct_calculator::evaluate("1 | 2 & 0", 4, true).unwrap();
+        assert_eq!("0", strict.get_results().get_value().get_unsigned());
+    }
+    {
+        let res = ct_calculator::evaluate("-0b1", 4, false).unwrap();
+        assert_eq!("-1", res.get_results().get_value().get_signed());
+        assert_eq!(false, res.get_results().get_flags().carry);
+    }
+    {
+        let res = ct_calculator::evaluate(" (0x_F) ", 4, false).unwrap();
+        assert_eq!(0, res.get_steps().len());
+        assert_eq!(true, res.get_results().get_flags().negative);
+    }
+
+    for bad in ["", "1 +", "(1", "1 2", "1 $ 2", "0xZZ"].iter() {
+        assert_eq!(
+            true,
+            ct_calculator::Evaluation::run(bad, 8, false).is_err(),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_literal() {
+    use ct_calculator::{Literal, ParseError};
+
+    let value = |input: &str, of: i32| Literal::parse(input, 10, of).map(|l| l.value);
+
+    assert_eq!(Ok(0b1010), value("0b1010", 4));
+    assert_eq!(Ok(0xFFFF), value("0x_FF_FF", 16));
+    assert_eq!(Ok(0o17), value("0o17", 8));
+    // in hex `b` is a digit, not the binary prefix
+    let hex = |input: &str| Literal::parse(input, 16, 16).map(|l| l.value);
+    assert_eq!(Ok(0xB1), hex("0B1"));
+    assert_eq!(Ok(0xB), hex("0b"));
+    assert_eq!(Ok(0x1F), hex("0x1F"));
+    assert_eq!(Ok(0o17), hex("0o17"));
+    assert_eq!(Ok(0xFB), value("-5", 8));
+    assert_eq!(Ok(-5), value("-5", 32));
+    assert_eq!(Ok(65), value("'A'", 8));
+    assert_eq!(Ok(10), value("'\\n'", 8));
+    assert_eq!(Ok(0xFF), value("8'hFF", 8));
+    assert_eq!(Ok(0xFFF3), value("8'shF3", 16));
+    assert_eq!(Ok(0x00F3), value("8'hF3", 16));
+    assert_eq!(Ok(0xF), Literal::parse("F", 16, 4).map(|l| l.value));
+
+    // signed and unsigned range
+    let lit = Literal::parse("-8", 10, 4).unwrap();
+    assert_eq!((true, false), (lit.fits_signed, lit.fits_unsigned));
+    let lit = Literal::parse("15", 10, 4).unwrap();
+    assert_eq!((false, true), (lit.fits_signed, lit.fits_unsigned));
+    let lit = Literal::parse("7", 10, 4).unwrap();
+    assert_eq!((true, true), (lit.fits_signed, lit.fits_unsigned));
+    assert_eq!("0111", lit.get_formatted().get_bin());
+
+    assert_eq!(
+        Err(ParseError::OutOfRange { value: 16, of: 4 }),
+        value("16", 4)
+    );
+    assert_eq!(
+        Err(ParseError::OutOfRange { value: -9, of: 4 }),
+        value("-9", 4)
+    );
+    assert_eq!(
+        Err(ParseError::InvalidDigit {
+            at: 4,
+            found: '2',
+            radix: 2
+        }),
+        value("0b10201", 8)
+    );
+    assert_eq!(Err(ParseError::MissingDigits { at: 2 }), value("0x", 8));
+    assert_eq!(Err(ParseError::Empty), value("  ", 8));
+    assert_eq!(Err(ParseError::SizeOverflow { size: 4 }), value("4'hFF", 8));
+    assert_eq!(
+        Err(ParseError::SizeMismatch { size: 16, of: 8 }),
+        value("16'h1", 8)
+    );
+    assert_eq!(Err(ParseError::InvalidChar { at: 0 }), value("'AB'", 8));
+    assert_eq!(Err(ParseError::UnsupportedWidth(64)), value("1", 64));
+
+    // the evaluator reads the same literals
+    let res = ct_calculator::evaluate("'A' + 8'h01", 8, false).unwrap();
+    assert_eq!("66", res.get_results().get_value().get_unsigned());
+    assert_eq!(
+        true,
+        ct_calculator::Evaluation::run("0x7F", 4, false).is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_hdl_literals() {
+    use ct_calculator::{Literal, ParseError};
+
+    let lit = Literal::parse("x\"3F\"", 10, 8).unwrap();
+    assert_eq!((0x3F, Some(8)), (lit.value, lit.get_size()));
+    let lit = Literal::parse("b\"1011\"", 10, 8).unwrap();
+    assert_eq!((0b1011, Some(4)), (lit.value, lit.get_size()));
+    let lit = Literal::parse("o\"17\"", 10, 8).unwrap();
+    assert_eq!((0o17, Some(6)), (lit.value, lit.get_size()));
+    assert_eq!(
+        Err(ParseError::SizeMismatch { size: 16, of: 8 }),
+        Literal::parse("x\"00FF\"", 10, 8)
+    );
+    assert_eq!(
+        Err(ParseError::InvalidBase { at: 2, found: 'q' }),
+        Literal::parse("8'q1", 10, 8)
+    );
+
+    // the width of the literal overrides the chosen one
+    let lit = Literal::parse_sized("4'b1011", 10, 32, true).unwrap();
+    assert_eq!(4, lit.of);
+    assert_eq!("-5", lit.get_formatted().get_signed());
+    let lit = Literal::parse_sized("8'shF3", 10, 32, false).unwrap();
+    assert_eq!(32, lit.of);
+    assert_eq!("-13", lit.get_formatted().get_signed());
+    assert_eq!(
+        Err(ParseError::UnsupportedWidth(6)),
+        Literal::parse_sized("6'h3F", 10, 8, true)
+    );
+
+    let value = ct_calculator::format("8'shF3", 8).unwrap();
+    assert_eq!("8'hF3", value.get_verilog());
+    assert_eq!("8'b11110011", value.get_verilog_bin());
+    assert_eq!("x\"F3\"", value.get_vhdl());
+    assert_eq!("\"11110011\"", value.get_vhdl_bin());
+    let value = ct_calculator::format("4'b1011", 8).unwrap();
+    assert_eq!("8'h0B", value.get_verilog());
+    let value = ct_calculator::format("x\"3F\"", 8).unwrap();
+    assert_eq!(0x3F, value.get_raw());
+
+    let res = ct_calculator::add(0b0111, 0b0001, 4).unwrap().get_value();
+    assert_eq!("4'h8", res.get_verilog());
+    assert_eq!("\"1000\"", res.get_vhdl_bin());
+}
+
+#[wasm_bindgen_test]
+fn test_representations() {
+    {
+        // -8 in 4 bit only exists in two's complement and excess-8
+        let value = ct_calculator::format(0b1000, 4).unwrap();
+        assert_eq!(None, value.get_sign_magnitude());
+        assert_eq!(None, value.get_ones());
+        assert_eq!(Some("0000".to_string()), value.get_excess());
+        assert_eq!("10", value.get_oct());
+    }
+    {
+        // -3 in 4 bit
+        let value = ct_calculator::format(-3, 4).unwrap();
+        assert_eq!(Some("1011".to_string()), value.get_sign_magnitude());
+        assert_eq!(Some("1100".to_string()), value.get_ones());
+        assert_eq!(Some("0101".to_string()), value.get_excess());
+        assert_eq!(Some("0100".to_string()), value.excess(7));
+        assert_eq!(None, value.excess(2));
+    }
+    {
+        let value = ct_calculator::format(100, 8).unwrap();
+        assert_eq!(Some("01100100".to_string()), value.get_sign_magnitude());
+        assert_eq!(Some("01100100".to_string()), value.get_ones());
+        assert_eq!(Some("11100100".to_string()), value.get_excess());
+        assert_eq!("144", value.get_oct());
+    }
+    {
+        let value = ct_calculator::format(i32::MIN, 32).unwrap();
+        assert_eq!(None, value.get_sign_magnitude());
+        assert_eq!("20000000000", value.get_oct());
+        let value = ct_calculator::format(-1, 16).unwrap();
+        assert_eq!(
+            Some("1000000000000001".to_string()),
+            value.get_sign_magnitude()
+        );
+        assert_eq!("177777", value.get_oct());
+    }
+    {
+        // excess-2^31 in 32 bit
+        let value = ct_calculator::format(0, 32).unwrap();
+        assert_eq!(Some(format!("1{}", "0".repeat(31))), value.get_excess());
+        let value = ct_calculator::format(5, 32).unwrap();
+        assert_eq!(Some(format!("1{:031b}", 5)), value.get_excess());
+        let value = ct_calculator::format(i32::MIN, 32).unwrap();
+        assert_eq!(Some("0".repeat(32)), value.get_excess());
+        assert_eq!(None, value.excess(-1));
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_bcd() {
+    let hex = |res: Result<Results, JsValue>| res.unwrap().get_value().get_hex();
+
+    assert_eq!("1234", hex(ct_calculator::bcd_encode(1234, 16)));
+    assert_eq!("9", hex(ct_calculator::bcd_encode(9, 4)));
+    assert_eq!("99999999", hex(ct_calculator::bcd_encode(99_999_999, 32)));
+    assert_eq!(
+        "1234",
+        ct_calculator::bcd_decode(0x1234, 16)
+            .unwrap()
+            .get_value()
+            .get_unsigned()
+    );
+
+    {
+        let res = ct_calculator::bcd_add(0x19, 0x28, 8).unwrap();
+        assert_eq!("41", res.get_binary().get_value().get_hex());
+        assert_eq!("47", res.get_decimal().get_value().get_hex());
+        assert_eq!(0x06, res.correction);
+        assert_eq!(true, res.half_carry);
+        assert_eq!(false, res.carry);
+    }
+    {
+        let res = ct_calculator::bcd_add(0x99, 0x01, 8).unwrap();
+        assert_eq!("00", res.get_decimal().get_value().get_hex());
+        assert_eq!(0x66, res.correction);
+        assert_eq!(true, res.carry);
+        assert_eq!(true, res.get_decimal().get_flags().zero);
+    }
+    {
+        let res = ct_calculator::bcd_add(5, 7, 4).unwrap();
+        assert_eq!("2", res.get_decimal().get_value().get_hex());
+        assert_eq!(6, res.correction);
+        assert_eq!(true, res.carry);
+    }
+    {
+        let res = ct_calculator::bcd_add(0x5000_0001, 0x5000_0009, 32).unwrap();
+        assert_eq!("00000010", res.get_decimal().get_value().get_hex());
+        assert_eq!(true, res.carry);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_bcd_human_error() {
+    assert_eq!(true, ct_calculator::bcd_encode(10, 4).is_err());
+    assert_eq!(true, ct_calculator::bcd_encode(-1, 8).is_err());
+    assert_eq!(true, ct_calculator::bcd_decode(0x1A, 8).is_err());
+    assert_eq!(true, ct_calculator::bcd_add(0x0A, 0x01, 8).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_float() {
+    use ct_calculator::FloatValue;
+
+    let half = ct_calculator::format(0x3C00, 16)
+        .unwrap()
+        .get_float()
+        .unwrap();
+    assert_eq!(
+        ("normal", 15, 0, 0),
+        (
+            half.get_class().as_str(),
+            half.biased_exponent,
+            half.exponent,
+            half.mantissa
+        )
+    );
+    assert_eq!("1", half.get_decimal());
+    assert_eq!(None, ct_calculator::format(0, 8).unwrap().get_float());
+
+    let tiny = FloatValue::of(0x0001, 16).unwrap();
+    assert_eq!("subnormal", tiny.get_class());
+    assert_eq!(-14, tiny.exponent);
+    assert_eq!("0.000000059604644775390625", tiny.get_decimal());
+
+    let single = FloatValue::of(0xBDCC_CCCD, 32).unwrap();
+    assert_eq!(true, single.sign);
+    assert_eq!("-0.100000001490116119384765625", single.get_decimal());
+
+    let double = FloatValue::of(0.1f64.to_bits(), 64).unwrap();
+    assert_eq!(
+        "0.1000000000000000055511151231257827021181583404541015625",
+        double.get_decimal()
+    );
+    assert_eq!(
+        "inf",
+        FloatValue::of(0x7F80_0000, 32).unwrap().get_decimal()
+    );
+    assert_eq!("nan", FloatValue::of(0x7E00, 16).unwrap().get_class());
+    assert_eq!("-0", FloatValue::of(0x8000, 16).unwrap().get_decimal());
+    assert_eq!("65504", FloatValue::of(0x7BFF, 16).unwrap().get_decimal());
+
+    // decimal to pattern
+    assert_eq!(0x2E66, FloatValue::parse("0.1", 16).unwrap().bits);
+    assert_eq!(0x7C00, FloatValue::parse("65520", 16).unwrap().bits);
+    assert_eq!(0x7BFF, FloatValue::parse("65519", 16).unwrap().bits);
+    assert_eq!(0x0001, FloatValue::parse("6e-8", 16).unwrap().bits);
+    assert_eq!(0x0000, FloatValue::parse("2.98e-8", 16).unwrap().bits);
+    assert_eq!(0x8400, FloatValue::parse("-6.1035e-5", 16).unwrap().bits);
+    assert_eq!(0x7BFF, FloatValue::parse("655.19e2", 16).unwrap().bits);
+    // just above and below a tie, the nearest double is the tie itself
+    assert_eq!(0x3C00, FloatValue::parse("1.00048828125", 16).unwrap().bits);
+    assert_eq!(
+        0x3C01,
+        FloatValue::parse("1.0004882812500000001", 16).unwrap().bits
+    );
+    assert_eq!(0x3C02, FloatValue::parse("1.00146484375", 16).unwrap().bits);
+    assert_eq!(
+        0x3C01,
+        FloatValue::parse("1.0014648437499999999", 16).unwrap().bits
+    );
+    assert_eq!(0x3DCC_CCCD, FloatValue::parse("0.1", 32).unwrap().bits);
+    assert_eq!(0xC0A0_0000, FloatValue::parse("-5", 32).unwrap().bits);
+    assert_eq!(1.5f64.to_bits(), FloatValue::parse("1.5", 64).unwrap().bits);
+    assert_eq!(true, FloatValue::parse("abc", 32).is_err());
+    assert_eq!(true, FloatValue::parse("1", 8).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_fixed() {
+    use ct_calculator::{api::FixedResults, QFormat};
+
+    let value = ct_calculator::format(0x18, 8).unwrap();
+    assert_eq!("1.5", value.fixed("Q4.4").unwrap());
+    assert_eq!("0.1875", value.fixed("Q1.7").unwrap());
+    let value = ct_calculator::format(0x8000, 16).unwrap();
+    assert_eq!("-1", value.fixed("Q15").unwrap());
+    assert_eq!("128", value.fixed("UQ8.8").unwrap());
+    assert_eq!(true, QFormat::parse("Q4.4", 16).is_err());
+    assert_eq!(true, QFormat::parse("Q0.8", 8).is_err());
+    assert_eq!(true, QFormat::parse("P4.4", 8).is_err());
+    assert_eq!(true, QFormat::parse("Q4294967295.1", 32).is_err());
+
+    let run = |op, left, right, format, rounding| {
+        FixedResults::run(op, left, right, format, 8, rounding).unwrap()
+    };
+
+    let res = run("mul", 0x18, 0x18, "Q4.4", "floor");
+    assert_eq!(
+        ("2.25", false, false),
+        (res.get_real().as_str(), res.overflow, res.precision_loss)
+    );
+
+    // 0.5 * 0.0625 is exactly between two steps
+    let mul = |left, right, rounding| run("mul", left, right, "Q4.4", rounding).get_real();
+    assert_eq!("0", mul(0x08, 0x01, "floor"));
+    assert_eq!("0", mul(0x08, 0x01, "zero"));
+    assert_eq!("0.0625", mul(0x08, 0x01, "nearest"));
+    assert_eq!("0", mul(0x08, 0x01, "even"));
+    assert_eq!("-0.0625", mul(0xF8, 0x01, "floor"));
+    assert_eq!("0", mul(0xF8, 0x01, "zero"));
+    assert_eq!("-0.0625", mul(0xF8, 0x01, "nearest"));
+    assert_eq!("0", mul(0xF8, 0x01, "even"));
+    assert_eq!(true, run("mul", 0x08, 0x01, "Q4.4", "even").precision_loss);
+
+    let res = run("mul", 0x40, 0x40, "Q4.4", "floor");
+    assert_eq!(true, res.overflow);
+    assert_eq!(true, res.get_results().get_flags().overflow);
+
+    let res = run("add", 0x40, 0x40, "Q1.7", "floor");
+    assert_eq!(("-1", true), (res.get_real().as_str(), res.overflow));
+    let res = run("add", 0x80, 0x80, "UQ4.4", "floor");
+    assert_eq!(("0", true), (res.get_real().as_str(), res.overflow));
+    let res = run("sub", 0x10, 0x20, "UQ4.4", "floor");
+    assert_eq!(("15", true), (res.get_real().as_str(), res.overflow));
+
+    assert_eq!(
+        true,
+        FixedResults::run("div", 1, 1, "Q4.4", 8, "floor").is_err()
+    );
+    assert_eq!(
+        true,
+        FixedResults::run("mul", 1, 1, "Q4.4", 8, "up").is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_chars() {
+    use ct_calculator::api::FormattedValue;
+
+    let chars = |value, of| ct_calculator::format(value, of).unwrap().get_chars();
+    assert_eq!(Some(vec!["A".to_string()]), chars(0x41, 8));
+    assert_eq!(Some(vec!["LF".to_string()]), chars(0x0A, 8));
+    assert_eq!(Some(vec!["ä".to_string()]), chars(0xE4, 8));
+    let expected = ["NUL", "SP", "DEL", "NEL"].map(String::from).to_vec();
+    assert_eq!(Some(expected), chars(0x00207F85, 32));
+    assert_eq!(None, chars(0x4, 4));
+
+    let utf8 = |value, of| ct_calculator::format(value, of).unwrap().get_utf8();
+    assert_eq!(Some("ä".to_string()), utf8(0xC3A4, 16));
+    assert_eq!(Some("Hi".to_string()), utf8(0x4869, 32));
+    assert_eq!(None, utf8(0xC3, 8));
+
+    let read = |input, of| FormattedValue::from_char_codepoint(input, of).map(|v| v.get_raw());
+    assert_eq!(Ok(0x41), read("A", 8));
+    assert_eq!(Ok(0x0A), read("LF", 8));
+    assert_eq!(Ok(0x00), read("nul", 8));
+    assert_eq!(Ok(0xE4), read("ä", 8));
+    assert_eq!(Ok(0x20AC), read("€", 16));
+    assert_eq!(true, read("€", 8).is_err());
+    assert_eq!(true, read("AB", 8).is_err());
+
+    let utf8_bytes = |input, of| FormattedValue::from_char_utf8(input, of).map(|v| v.get_raw());
+    assert_eq!(Ok(0x41), utf8_bytes("A", 8));
+    assert_eq!(Ok(0xC3A4), utf8_bytes("ä", 16));
+    assert_eq!(Ok(0xE282AC), utf8_bytes("€", 32));
+    assert_eq!(true, utf8_bytes("€", 16).is_err());
+    assert_eq!(true, utf8_bytes("A", 4).is_err());
+
+    // the code point is what a character literal reads, the UTF-8 bytes are
+    // what `get_utf8` reads back
+    for c in ["A", "~", "ä", "ÿ", "€", "😀"] {
+        let literal = ct_calculator::Literal::parse(&format!("'{}'", c), 10, 32).unwrap();
+        assert_eq!(
+            Ok(literal.get_formatted()),
+            FormattedValue::from_char_codepoint(c, 32)
+        );
+        let bytes = FormattedValue::from_char_utf8(c, 32).unwrap();
+        assert_eq!(Some(c.to_string()), bytes.get_utf8());
+    }
+    assert_eq!(true, read("A", 4).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_grouping() {
+    use ct_calculator::FormatOptions;
+
+    let value = ct_calculator::format(0xA5F0, 16).unwrap();
+    let plain = FormatOptions::new();
+    assert_eq!("1010010111110000", value.bin_with(&plain));
+    assert_eq!("A5F0", value.hex_with(&plain));
+
+    let nibbles = FormatOptions::nibbles('_');
+    assert_eq!("1010_0101_1111_0000", value.bin_with(&nibbles));
+    assert_eq!("A5_F0", value.hex_with(&nibbles));
+    assert_eq!("15   11   7    3", value.ruler(&nibbles));
+
+    let options = FormatOptions {
+        prefix: true,
+        lowercase: true,
+        ..FormatOptions::bytes(' ')
+    };
+    assert_eq!("0b10100101 11110000", value.bin_with(&options));
+    assert_eq!("0xa5 f0", value.hex_with(&options));
+    assert_eq!("  15       7", value.ruler(&options));
+
+    let results = ct_calculator::add(0x0F, 1, 8).unwrap().get_value();
+    assert_eq!("0001'0000", results.bin_with(&FormatOptions::nibbles('\'')));
+    assert_eq!("7", results.ruler(&plain));
+
+    // the options stay with the value, the other notations keep plain digits
+    let grouped = value.with_options(&options);
+    assert_eq!("0b10100101 11110000", grouped.get_bin());
+    assert_eq!("0xa5 f0", grouped.get_hex());
+    assert_eq!("16'hA5F0", grouped.get_verilog());
+    assert_eq!("1010010111110000", grouped.bin_with(&plain));
+    assert_eq!(value, grouped.with_options(&plain));
+
+    let grouped = ct_calculator::add(0x7F, 1, 8)
+        .unwrap()
+        .with_options(&nibbles);
+    assert_eq!("1000_0000", grouped.get_value().get_bin());
+    assert_eq!("-128", grouped.get_value().get_signed());
+    assert_eq!(true, grouped.get_flags().overflow);
+}
+
+#[wasm_bindgen_test]
+fn test_session() {
+    use ct_calculator::Session;
+
+    let mut session = Session::new(8).unwrap();
+    assert_eq!(true, Session::new(12).is_none());
+
+    session.run("r0 = 0x7F").unwrap();
+    session.run("adds r0, r0, #1").unwrap();
+    assert_eq!(Some(0x80), session.register(0));
+    let flags = session.flags();
+    assert_eq!(
+        (true, true, false),
+        (flags.negative, flags.overflow, flags.carry)
+    );
+
+    // a 16-bit addition as two 8-bit halves
+    session.run("r1 = 0xFF").unwrap();
+    session.run("r2 = 0x01").unwrap();
+    session.run("adds r1, r1, #1").unwrap();
+    session.run("adcs r2, r2, #0").unwrap();
+    assert_eq!(
+        (Some(0x00), Some(0x02)),
+        (session.register(1), session.register(2))
+    );
+
+    // without the `s` the flags are kept
+    session.run("subs r3, r2, #3").unwrap();
+    assert_eq!(false, session.flags().carry);
+    session.run("add r3, r3, #1").unwrap();
+    assert_eq!(false, session.flags().carry);
+    session.run("sbcs r4, r2, #0").unwrap();
+    assert_eq!(Some(0x01), session.register(4));
+
+    session.run("r0 + 1").unwrap();
+    session.run("r5 = _ * 2").unwrap_err();
+    session.run("r5 = _ + 'A'").unwrap();
+    assert_eq!(Some(0xC2), session.register(5));
+
+    session.run("width 16").unwrap();
+    assert_eq!(16, session.of());
+    let before = session.history().len();
+    session.run("!!").unwrap();
+    assert_eq!(before + 1, session.history().len());
+    assert_eq!(true, session.run("width 12").is_err());
+    assert_eq!(true, session.run("adds r16, r0, #1").is_err());
+    assert_eq!(true, session.run("foo").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_conditions() {
+    use ct_calculator::api::{SIGNED_CONDITIONS, UNSIGNED_CONDITIONS};
+
+    // 3 - 5, lower and less than
+    let flags = ct_calculator::sub(3, 5, 8).unwrap().get_flags();
+    let holds = |code| flags.condition(code).unwrap();
+    assert_eq!(
+        (false, true, false),
+        (holds("EQ"), holds("NE"), holds("HS"))
+    );
+    assert_eq!((true, true, false), (holds("LO"), holds("LT"), holds("GE")));
+    assert_eq!((true, false), (holds("LE"), holds("GT")));
+
+    // -128 - 1 overflows, still less than
+    let flags = ct_calculator::sub(-128, 1, 8).unwrap().get_flags();
+    assert_eq!(Some(true), flags.condition("VS"));
+    assert_eq!(Some(true), flags.condition("lt"));
+    assert_eq!(None, flags.condition("XX"));
+
+    for (code, _) in UNSIGNED_CONDITIONS.iter().chain(SIGNED_CONDITIONS.iter()) {
+        assert_eq!(true, flags.condition(code).is_some());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn test_server() {
+    use ct_calculator::server::respond;
+
+    let answer = respond(r#"{"op":"add","left":127,"right":"0x01","width":8,"id":7}"#);
+    assert_eq!(true, answer.starts_with(r#"{"id":7,"op":"add","width":8,"#));
+    assert_eq!(true, answer.contains(r#""hex":"80""#));
+    assert_eq!(true, answer.contains(r#""overflow":true"#));
+    assert_eq!(true, answer.contains(r#""VS":true"#));
+
+    let answer = respond(
+        r#"[{"op":"and","left":12,"right":10,"width":4},{"op":"add","left":1,"right":1,"width":12}]"#,
+    );
+    assert_eq!(
+        true,
+        answer.starts_with(r#"[{"op":"and","width":4,"value":{"raw":8"#)
+    );
+    assert_eq!(
+        true,
+        answer.ends_with(
+            r#"{"error":{"code":"unsupported_width","message":"unsupported width 12"}}]"#
+        )
+    );
+
+    let answer = respond(r#"{"op":"mul","left":1,"right":1,"id":"a"}"#);
+    assert_eq!(true, answer.starts_with(r#"{"id":"a","error":{"#));
+
+    let answer = respond(r#"{"op":"format","value":-1,"width":4}"#);
+    assert_eq!(true, answer.contains(r#""bin":"1111""#));
+
+    let code = |line| {
+        let answer = respond(line);
+        let start = answer.find(r#""code":""#).unwrap() + 8;
+        answer[start..].split('"').next().unwrap().to_string()
+    };
+    assert_eq!("parse_error", code("{op:add}"));
+    assert_eq!("invalid_request", code("[]"));
+    assert_eq!("invalid_request", code(r#"{"op":"add","left":1}"#));
+    assert_eq!(
+        "invalid_operand",
+        code(r#"{"op":"add","left":256,"right":1}"#)
+    );
+    assert_eq!("unknown_op", code(r#"{"op":"mul","left":1,"right":1}"#));
+
+    // deep nesting is refused instead of running out of stack
+    let nested = "[".repeat(200_000);
+    assert_eq!("parse_error", code(&nested));
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn test_serde() {
+    use ct_calculator::{
+        api::{BcdResults, FixedResults, FormattedValue, LaneResults, Request},
+        FloatValue,
+    };
+
+    let request: Request =
+        serde_json::from_str(r#"{"op":"sub","left":3,"right":5,"width":8}"#).unwrap();
+    let results = request.run().unwrap();
+    let json = serde_json::to_string(&results).unwrap();
+    assert_eq!(
+        r#"{"flags":{"zero":false,"negative":true,"overflow":false,"carry":false,"borrow":true},"values":{"raw":254,"signed":"-2","unsigned":"254","bin":"11111110","hex":"FE"},"field":null}"#,
+        json
+    );
+    let back: Results = serde_json::from_str(&json).unwrap();
+    assert_eq!(results, back);
+
+    let value = ct_calculator::format(-1, 4).unwrap();
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(
+        value,
+        serde_json::from_str::<FormattedValue>(&json).unwrap()
+    );
+
+    // the display fields have to agree with the bits
+    let json_results = |results: &Results| serde_json::to_string(results).unwrap();
+    let borrow = json_results(&results).replace(r#""borrow":true"#, r#""borrow":false"#);
+    assert_eq!(true, serde_json::from_str::<Results>(&borrow).is_err());
+    let hex = json_results(&results).replace(r#""hex":"FE""#, r#""hex":"FF""#);
+    assert_eq!(true, serde_json::from_str::<Results>(&hex).is_err());
+    let raw = json.replace(r#""raw":15"#, r#""raw":255"#);
+    assert_eq!(true, serde_json::from_str::<FormattedValue>(&raw).is_err());
+    let field = ct_calculator::ubfx(0xF0, 4, 4, 8).unwrap();
+    let back: Results = serde_json::from_str(&json_results(&field)).unwrap();
+    assert_eq!(field, back);
+    let moved = json_results(&field).replace("[1111]", "1111");
+    assert_eq!(true, serde_json::from_str::<Results>(&moved).is_err());
+
+    // so do the parts of the composite results
+    let lanes = ct_calculator::uadd8(0x01FF_7F80, 0x0101_0180);
+    let json = serde_json::to_string(&lanes).unwrap();
+    assert_eq!(lanes, serde_json::from_str::<LaneResults>(&json).unwrap());
+    let ge = json.replace(r#""ge":5"#, r#""ge":4"#);
+    assert_eq!(true, serde_json::from_str::<LaneResults>(&ge).is_err());
+    let packed = json.replace(r#""raw":33587200"#, r#""raw":33587201"#);
+    assert_eq!(true, serde_json::from_str::<LaneResults>(&packed).is_err());
+
+    let bcd = ct_calculator::bcd_add(0x19, 0x28, 8).unwrap();
+    let json = serde_json::to_string(&bcd).unwrap();
+    assert_eq!(bcd, serde_json::from_str::<BcdResults>(&json).unwrap());
+    let correction = json.replace(r#""correction":6"#, r#""correction":0"#);
+    assert_eq!(
+        true,
+        serde_json::from_str::<BcdResults>(&correction).is_err()
+    );
+
+    let fixed = ct_calculator::fixed("mul", 0x18, 0x28, "Q4.4", 8, "even").unwrap();
+    let json = serde_json::to_string(&fixed).unwrap();
+    assert_eq!(fixed, serde_json::from_str::<FixedResults>(&json).unwrap());
+    let real = json.replace(r#""real":"3.75""#, r#""real":"3.7""#);
+    assert_eq!(true, serde_json::from_str::<FixedResults>(&real).is_err());
+
+    let float = ct_calculator::parse_float("-1.5", 32).unwrap();
+    let json = serde_json::to_string(&float).unwrap();
+    assert_eq!(float, serde_json::from_str::<FloatValue>(&json).unwrap());
+    let decimal = json.replace(r#""decimal":"-1.5""#, r#""decimal":"-2.5""#);
+    assert_eq!(true, serde_json::from_str::<FloatValue>(&decimal).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_object() {
+    use js_sys::Reflect;
+
+    let get = |target: &JsValue, key: &str| Reflect::get(target, &JsValue::from(key)).unwrap();
+
+    let object = ct_calculator::calculate("add", 127, 1, 8).unwrap();
+    assert_eq!(Some(128.0), get(&object, "raw").as_f64());
+    assert_eq!(Some(-128.0), get(&object, "signed").as_f64());
+    assert_eq!(Some(128.0), get(&object, "unsigned").as_f64());
+    assert_eq!(
+        Some("10000000".to_string()),
+        get(&object, "bin").as_string()
+    );
+    assert_eq!(Some("80".to_string()), get(&object, "hex").as_string());
+    assert_eq!(
+        Some(true),
+        get(&get(&object, "flags"), "overflow").as_bool()
+    );
+    assert_eq!(Some(true), get(&get(&object, "conditions"), "VS").as_bool());
+    assert_eq!(
+        Some(false),
+        get(&get(&object, "conditions"), "EQ").as_bool()
+    );
+
+    let object = ct_calculator::sub(0, 1, 32).unwrap().to_object();
+    assert_eq!(Some(-1.0), get(&object, "signed").as_f64());
+    assert_eq!(Some(4294967295.0), get(&object, "unsigned").as_f64());
+
+    assert_eq!(true, ct_calculator::calculate("add", 1, 1, 12).is_err());
+    assert_eq!(true, ct_calculator::calculate("mul", 1, 1, 8).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_batch() {
+    use ct_calculator::{BatchResults, FLAG_CARRY, FLAG_NEGATIVE, FLAG_OVERFLOW, FLAG_ZERO};
+
+    let batch = BatchResults::run("add", &[127, 255, 1], &[1, 1, 2], 8).unwrap();
+    assert_eq!(vec![128, 0, 3], batch.get_values());
+    assert_eq!(
+        vec![FLAG_NEGATIVE | FLAG_OVERFLOW, FLAG_ZERO | FLAG_CARRY, 0],
+        batch.get_flags()
+    );
+
+    let batch = BatchResults::run("sub", &[0, 5], &[1, 5], 4).unwrap();
+    assert_eq!(vec![15, 0], batch.get_values());
+    assert_eq!(
+        vec![FLAG_NEGATIVE, FLAG_ZERO | FLAG_CARRY],
+        batch.get_flags()
+    );
+
+    let batch = BatchResults::run("nand", &[0xF0F0], &[0xFF00], 16).unwrap();
+    assert_eq!(vec![0x0FFF], batch.get_values());
+    assert_eq!(vec![0], batch.get_flags());
+
+    // the same flags as one operation at a time
+    let operands: Vec<u32> = (0..16).collect();
+    for op in [
+        "add", "sub", "and", "nand", "or", "xor", "nor", "xnor", "orn", "imply",
+    ] {
+        for left in 0..16 {
+            let batch = BatchResults::run(op, &[left; 16], &operands, 4).unwrap();
+            for right in 0..16 {
+                let results = ct_calculator::operation(op, left as i32, right as i32, 4).unwrap();
+                let flags = results.get_flags();
+                let packed = batch.get_flags()[right as usize];
+                assert_eq!(
+                    results.get_value().get_raw(),
+                    batch.get_values()[right as usize]
+                );
+                assert_eq!(flags.negative, packed & FLAG_NEGATIVE != 0);
+                assert_eq!(flags.zero, packed & FLAG_ZERO != 0);
+                assert_eq!(flags.carry, packed & FLAG_CARRY != 0);
+                assert_eq!(flags.overflow, packed & FLAG_OVERFLOW != 0);
+            }
+        }
+    }
+
+    assert_eq!(true, BatchResults::run("add", &[1, 2], &[1], 8).is_err());
+    assert_eq!(true, BatchResults::run("mul", &[1], &[1], 8).is_err());
+    // no truth table aliases, just like `operation`
+    for op in ["true", "bic", "AND"] {
+        assert_eq!(true, ct_calculator::operation(op, 1, 1, 8).is_err());
+        assert_eq!(true, BatchResults::run(op, &[1], &[1], 8).is_err());
+    }
+    assert_eq!(true, BatchResults::run("add", &[1], &[1], 12).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_flag_table() {
+    use ct_calculator::{
+        FlagTable, TableFormat, FLAG_CARRY, FLAG_NEGATIVE, FLAG_OVERFLOW, FLAG_ZERO,
+    };
+
+    let table = FlagTable::new("add", 4).unwrap();
+    assert_eq!(Some((8, FLAG_NEGATIVE | FLAG_OVERFLOW)), table.get(7, 1));
+    assert_eq!(Some((0, FLAG_ZERO | FLAG_CARRY)), table.get(0xF, 1));
+    assert_eq!(None, table.get(16, 0));
+
+    let csv = table.write(TableFormat::Csv);
+    assert_eq!(257, csv.lines().count());
+    assert_eq!(Some("left,right,result,N,Z,C,V"), csv.lines().next());
+    assert_eq!(true, csv.contains("\n7,1,8,1,0,0,1\n"));
+
+    let markdown = FlagTable::new("sub", 4)
+        .unwrap()
+        .write(TableFormat::parse("md").unwrap());
+    assert_eq!(
+        true,
+        markdown.contains("| **1** | 1 --C- | 0 -ZC- | F N--- |")
+    );
+    assert_eq!(16 + 4, markdown.lines().count());
+
+    let html = FlagTable::new("and", 8).unwrap().write(TableFormat::Html);
+    assert_eq!(256 + 1, html.matches("<tr>").count());
+    assert_eq!(true, html.contains("<td>FF N---</td></tr>"));
+
+    assert_eq!(true, FlagTable::new("add", 16).is_err());
+    assert_eq!(true, FlagTable::new("mul", 4).is_err());
+    assert_eq!(true, TableFormat::parse("pdf").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_exercise() {
+    use ct_calculator::{Difficulty, Exercise, FlagTarget, Generator};
+
+    // the same seed gives the same exercises
+    let questions = |seed| {
+        let mut generator = Generator::new(seed, 8).unwrap();
+        (0..5)
+            .map(|_| generator.random("add", Difficulty::Medium).unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(questions(42), questions(42));
+    assert_ne!(questions(42), questions(43));
+
+    let mut generator = Generator::new(7, 8).unwrap();
+    for _ in 0..20 {
+        let easy = generator.random("sub", Difficulty::Easy).unwrap();
+        assert_eq!(true, easy.left() < 64 && easy.right() <= easy.left());
+        assert_eq!(false, easy.get_results().get_flags().negative);
+
+        let hard = generator.random("add", Difficulty::Hard).unwrap();
+        let flags = hard.get_results().get_flags();
+        assert_eq!(true, flags.carry || flags.overflow);
+
+        let hard = generator.random("sub", Difficulty::Hard).unwrap();
+        let flags = hard.get_results().get_flags();
+        assert_eq!(true, flags.borrow || flags.overflow);
+    }
+    assert_eq!(true, generator.random("and", Difficulty::Hard).is_err());
+
+    let target = FlagTarget::parse("C=0 V=1").unwrap();
+    assert_eq!(Some(false), target.carry);
+    assert_eq!(None, target.zero);
+    for of in [4, 8, 16, 32] {
+        let mut generator = Generator::new(1, of).unwrap();
+        let exercise = generator.targeted("sub", target).unwrap();
+        assert_eq!(true, target.matches(&exercise.get_results().get_flags()));
+        assert_eq!(
+            format!("Choose {} bit operands so that SUBS sets C=0 and V=1.", of),
+            exercise.get_question()
+        );
+        // the answer key is the calculator's
+        let results =
+            ct_calculator::operation("sub", exercise.left() as i32, exercise.right() as i32, of)
+                .unwrap();
+        assert_eq!(results, exercise.get_results());
+    }
+
+    // a zero result is never negative
+    let impossible = FlagTarget::parse("z=1,n=1").unwrap();
+    assert_eq!(
+        Err("no 8 bit operands make ADDS set N=1 and Z=1".to_string()),
+        Generator::new(1, 8).unwrap().targeted("add", impossible)
+    );
+
+    let exercise = Exercise::new("add", 0x7F, 1, 8).unwrap();
+    assert_eq!(
+        "Compute ADDS 0x7F, 0x01 in 8 bit, give the result and NZCV.",
+        exercise.get_question()
+    );
+    assert_eq!(None, Exercise::new("add", 1, 1, 12));
+    assert_eq!(None, Generator::new(1, 12).map(|_| ()));
+    assert_eq!(true, FlagTarget::parse("X=1").is_err());
+    assert_eq!(true, FlagTarget::parse("").is_err());
+    assert_eq!(true, Difficulty::parse("insane").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_check() {
+    use ct_calculator::{Answer, Exercise, Feedback};
+
+    let causes = |feedback: &Feedback| {
+        feedback
+            .get_mismatches()
+            .iter()
+            .map(|m| (m.get_field(), m.get_cause()))
+            .collect::<Vec<_>>()
+    };
+
+    // 127 + 1 in 8 bit is 0x80, N and V set
+    let mut answer = Answer::new();
+    answer.bin = Some("1000 0000".to_string());
+    answer.hex = Some("0x80".to_string());
+    answer.unsigned = Some("128".to_string());
+    answer.signed = Some("-128".to_string());
+    answer.negative = Some(true);
+    answer.zero = Some(false);
+    answer.carry = Some(false);
+    answer.overflow = Some(true);
+    answer.claim("vs", true);
+    answer.claim("LT", false);
+    let feedback = Feedback::check("add", 127, 1, 8, &answer).unwrap();
+    assert_eq!(true, feedback.get_correct());
+    assert_eq!(10, feedback.get_checked());
+    assert_eq!("all 10 fields are right", feedback.summary());
+
+    // the classic mistakes
+    let mut answer = Answer::new();
+    answer.signed = Some("128".to_string());
+    answer.carry = Some(true);
+    answer.overflow = Some(false);
+    let feedback = Feedback::check("add", 127, 1, 8, &answer).unwrap();
+    assert_eq!(
+        vec![
+            (
+                "signed".to_string(),
+                "the exact result does not fit into 8 bit, V is set".to_string()
+            ),
+            (
+                "C".to_string(),
+                "carry and overflow mixed up, C is the unsigned and V the signed overflow"
+                    .to_string()
+            ),
+            (
+                "V".to_string(),
+                "carry and overflow mixed up, C is the unsigned and V the signed overflow"
+                    .to_string()
+            ),
+        ],
+        causes(&feedback)
+    );
+
+    // 5 - 3 does not borrow, so C is set
+    let mut answer = Answer::new();
+    answer.unsigned = Some("1".to_string());
+    answer.carry = Some(false);
+    answer.claim("HS", false);
+    answer.claim("EQ", true);
+    let feedback = Feedback::check("sub", 5, 3, 8, &answer).unwrap();
+    assert_eq!(
+        vec![
+            (
+                "unsigned".to_string(),
+                "the two's complement of the right operand needs the +1".to_string()
+            ),
+            (
+                "C".to_string(),
+                "carry and borrow mixed up, SUBS sets C=1 when there is no borrow".to_string()
+            ),
+            ("HS".to_string(), "follows from the wrong flags".to_string()),
+            ("EQ".to_string(), "EQ holds when Z=1".to_string()),
+        ],
+        causes(&feedback)
+    );
+    let mismatch = &feedback.get_mismatches()[1];
+    assert_eq!(
+        ("1", "0"),
+        (&*mismatch.get_expected(), &*mismatch.get_given())
+    );
+
+    let mut answer = Answer::new();
+    answer.unsigned = Some("-2".to_string());
+    answer.hex = Some("FE".to_string());
+    answer.bin = Some("0b0111_1111".to_string());
+    answer.signed = Some("x".to_string());
+    let feedback = Feedback::check("sub", 3, 5, 8, &answer).unwrap();
+    assert_eq!(
+        vec![
+            (
+                "bin".to_string(),
+                "the bits are reversed, the MSB comes first".to_string()
+            ),
+            (
+                "unsigned".to_string(),
+                "this is the signed value, read the bits as unsigned".to_string()
+            ),
+            ("signed".to_string(), "'x' is no signed number".to_string()),
+        ],
+        causes(&feedback)
+    );
+    assert_eq!(4, feedback.get_checked());
+
+    let mut answer = Answer::new();
+    answer.hex = Some("0x1FF".to_string());
+    answer.carry = Some(true);
+    let feedback = Feedback::check("xor", 0xF0, 0x0F, 8, &answer).unwrap();
+    assert_eq!(
+        vec![
+            ("hex".to_string(), "more digits than 8 bit".to_string()),
+            (
+                "C".to_string(),
+                "the logical operations clear C and V here".to_string()
+            ),
+        ],
+        causes(&feedback)
+    );
+
+    let exercise = Exercise::new("add", 0xF, 1, 4).unwrap();
+    let mut answer = Answer::new();
+    answer.zero = Some(true);
+    assert_eq!(true, exercise.check(&answer).get_correct());
+
+    let mut answer = Answer::new();
+    answer.claim("XX", true);
+    let feedback = exercise.check(&answer);
+    assert_eq!(false, feedback.get_correct());
+    assert_eq!(
+        vec![("XX".to_string(), "unknown condition 'XX'".to_string())],
+        causes(&feedback)
+    );
+    assert_eq!(true, Feedback::check("add", 1, 1, 8, &answer).is_ok());
+    assert_eq!(
+        true,
+        Feedback::check("mul", 1, 1, 8, &Answer::new()).is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_exam() {
+    use ct_calculator::{Exam, Exercise, FlagTarget, Generator, SheetFormat};
+
+    let mut exam = Exam::new("Quiz 1 & 2");
+    exam.push(&Exercise::new("add", 0x7F, 1, 8).unwrap());
+    let mut generator = Generator::new(3, 4).unwrap();
+    let targeted = generator
+        .targeted("sub", FlagTarget::parse("C=0").unwrap())
+        .unwrap();
+    exam.push(&targeted);
+    assert_eq!(2, exam.get_len());
+
+    let sheet = exam.sheet(SheetFormat::Markdown);
+    assert_eq!(Some("# Quiz 1 & 2"), sheet.lines().next());
+    assert_eq!(
+        true,
+        sheet.contains("## 1. Compute ADDS 0x7F, 0x01 in 8 bit, give the result and NZCV.")
+    );
+    assert_eq!(
+        true,
+        sheet.contains("## 2. Choose 4 bit operands so that SUBS sets C=0.")
+    );
+    assert_eq!(false, sheet.contains("10000000"));
+
+    let solutions = exam.solutions(SheetFormat::Markdown);
+    assert_eq!(Some("# Quiz 1 & 2: Solutions"), solutions.lines().next());
+    assert_eq!(
+        true,
+        solutions.contains("| result | `10000000` | `80` | 128 | -128 |")
+    );
+    assert_eq!(true, solutions.contains("| 1 | 0 | 0 | 1 |"));
+    let left = targeted.get_left();
+    assert_eq!(
+        true,
+        solutions.contains(&format!(
+            "| left | `{}` | `{}` |",
+            left.get_bin(),
+            left.get_hex()
+        ))
+    );
+
+    let latex = exam.solutions(SheetFormat::parse("tex").unwrap());
+    assert_eq!(true, latex.starts_with("\\documentclass{article}"));
+    assert_eq!(true, latex.contains("\\section*{Quiz 1 \\& 2: Solutions}"));
+    assert_eq!(
+        true,
+        latex.contains("result & \\texttt{10000000} & \\texttt{80} & 128 & -128")
+    );
+    let blank = exam.sheet(SheetFormat::Latex);
+    assert_eq!(
+        true,
+        blank.contains("result &  &  &  &  \\rule{0pt}{4ex} \\\\")
+    );
+
+    assert_eq!(
+        true,
+        blank.contains("\\begin{tabular}{|l|p{1.9cm}|p{2cm}|p{2cm}|p{2cm}|}")
+    );
+
+    // the bin column grows with the width, Markdown text is escaped too
+    let mut wide = Exam::new("Quiz *3* | [b]");
+    wide.push(&Exercise::new("add", u32::MAX, 1, 32).unwrap());
+    let latex = wide.solutions(SheetFormat::Latex);
+    assert_eq!(true, latex.contains("{|l|p{6.7cm}|"));
+    assert_eq!(
+        Some("# Quiz \\*3\\* \\| \\[b\\]"),
+        wide.sheet(SheetFormat::Markdown).lines().next()
+    );
+
+    let html = exam.solutions(SheetFormat::Html);
+    assert_eq!(true, html.starts_with("<!DOCTYPE html>"));
+    assert_eq!(true, html.contains("<h1>Quiz 1 &amp; 2: Solutions</h1>"));
+    assert_eq!(true, html.contains("<td class=\"mono\">10000000</td>"));
+    assert_eq!(2, html.matches("<li>").count());
+    let blank = exam.sheet(SheetFormat::Html);
+    assert_eq!(false, blank.contains("10000000"));
+
+    assert_eq!(true, SheetFormat::parse("docx").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_history() {
+    use ct_calculator::History;
+
+    let mut history = History::new();
+    assert_eq!(false, history.get_can_undo());
+    assert_eq!(None, history.get_current());
+
+    let results = history.record("add", 127, 1, 8).unwrap();
+    assert_eq!(128, results.get_value().get_raw());
+    history.record("sub", 3, 5, 4).unwrap();
+    // the same operation again is no new step
+    history.record("sub", 3, 5, 4).unwrap();
+    history.record("nand", 0xF0, 0xFF, 8).unwrap();
+    assert_eq!(3, history.len());
+
+    assert_eq!(true, history.undo());
+    let current = history.get_current().unwrap();
+    assert_eq!(("sub".to_string(), 4), (current.get_op(), current.get_of()));
+    assert_eq!("E", current.get_results().get_value().get_hex());
+    assert_eq!(true, history.get_can_redo());
+    assert_eq!(history.get_entries().len(), history.len());
+    assert_eq!(2, history.len());
+
+    assert_eq!(true, history.redo());
+    assert_eq!("nand", history.get_current().unwrap().get_op());
+    assert_eq!(false, history.redo());
+
+    // recording after an undo drops the undone steps
+    history.undo();
+    history.undo();
+    history.record("xor", -1, 0x0F, 8).unwrap();
+    assert_eq!(false, history.get_can_redo());
+    assert_eq!(
+        "# ct-calc history\nadd 8 0x7F 0x01\nxor 8 0xFF 0x0F",
+        history.script()
+    );
+
+    let replayed = History::replay(&history.script()).unwrap();
+    assert_eq!(history.get_entries(), replayed.get_entries());
+    assert_eq!(history.script(), replayed.script());
+
+    history.undo();
+    history.undo();
+    assert_eq!(false, history.undo());
+    assert_eq!(None, history.get_current());
+    assert_eq!("# ct-calc history", history.script());
+    assert_eq!(true, history.is_empty());
+
+    let replayed = History::replay("\n# lecture 3\nadd 4 -1 1\n  or 16 0b1010 5  \n").unwrap();
+    assert_eq!(2, replayed.get_entries().len());
+    assert_eq!(
+        true,
+        replayed.get_entries()[0].get_results().get_flags().carry
+    );
+
+    assert_eq!(
+        Err("line 2: unknown operation 'mul'".to_string()),
+        History::replay("add 8 1 1\nmul 8 1 1")
+    );
+    assert_eq!(true, History::replay("add 12 1 1").is_err());
+    assert_eq!(true, History::replay("add 8 1").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_session_sub_flags() {
+    use ct_calculator::Session;
+
+    // `subs` and `cmp` report the same flags as `sub`, C is the inverted
+    // borrow, so subtracting zero sets it
+    for of in [4, 8] {
+        for (left, right) in [(5, 0), (0, 0), (0, 1), (3, 5), (5, 3), (8, 1), (7, 15)] {
+            let expected = ct_calculator::sub(left, right, of).unwrap().get_flags();
+            for line in ["subs r1, r0, r2", "cmp r0, r2"] {
+                let mut session = Session::new(of).unwrap();
+                session.run(&format!("r0 = {}", left)).unwrap();
+                session.run(&format!("r2 = {}", right)).unwrap();
+                session.run(line).unwrap();
+                assert_eq!(
+                    &expected,
+                    session.flags(),
+                    "{} with {} and {}",
+                    line,
+                    left,
+                    right
+                );
+            }
+        }
+    }
+    assert_eq!(true, ct_calculator::sub(5, 0, 8).unwrap().get_flags().carry);
+}
+
+#[wasm_bindgen_test]
+fn test_js_options() {
+    use ct_calculator::{api::format_js, FormatOptions};
+
+    let value = format_js(JsValue::from("0x7F"), 8, Some(FormatOptions::nibbles('_'))).unwrap();
+    assert_eq!("0111_1111", value.get_bin());
+    let value = format_js(JsValue::from(-1), 4, None).unwrap();
+    assert_eq!("1111", value.get_bin());
+    assert_eq!(true, format_js(JsValue::TRUE, 8, None).is_err());
+
+    let results = ct_calculator::add_js(0x7F, 1, 8, Some(FormatOptions::bytes(' '))).unwrap();
+    assert_eq!("10000000", results.get_value().get_bin());
+    assert_eq!(true, ct_calculator::sub_js(1, 1, 12, None).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn test_cli() {
+    use ct_calculator::cli::{run, Args};
+
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    let args = parse("-w 16 -g nibble -p -l add 0x7FFF -1").unwrap();
+    assert_eq!(16, args.of);
+    assert_eq!((4, 2, true, true), {
+        let options = args.options;
+        (
+            options.bin_group,
+            options.hex_group,
+            options.prefix,
+            options.lowercase,
+        )
+    });
+    // negative values are no options
+    assert_eq!(vec!["add", "0x7FFF", "-1"], args.command);
+    assert_eq!(true, parse("-w 12 add 1 1").is_err());
+    assert_eq!(true, parse("--frobnicate").is_err());
+    assert_eq!(true, parse("-g word format 1").is_err());
+    assert_eq!(true, parse("-s ab format 1").is_err());
+    assert_eq!(true, parse("-w").is_err());
+
+    let out = run(&parse("-w 4 add 7 1").unwrap()).unwrap();
+    assert_eq!(
+        "add, 4 bit\n         hex  bin   unsigned  signed\nleft       7  0111         7       7\n\
+         right      1  0001         1       1\nresult     8  1000         8      -8\n\
+         flags    N=1 Z=0 C=0 V=1",
+        out
+    );
+
+    let out = run(&parse("-w 16 -g nibble -r format -1").unwrap()).unwrap();
+    assert_eq!(
+        true,
+        out.ends_with("value    FF_FF  1111_1111_1111_1111     65535      -1")
+    );
+    assert_eq!(true, out.contains("15   11   7    3"));
+
+    let out = run(&parse("--json -w 4 sub 0 1").unwrap()).unwrap();
+    assert_eq!(
+        true,
+        out.starts_with(r#"{"op":"sub","of":4,"left":{"raw":0,"unsigned":"0","signed":"0""#)
+    );
+    assert_eq!(true, out.ends_with(r#""carry":false,"borrow":true}}"#));
+    let out = run(&parse("-j format 'A'").unwrap()).unwrap();
+    assert_eq!(
+        r#"{"of":8,"value":{"raw":65,"unsigned":"65","signed":"65","hex":"41","bin":"01000001"},"oct":"101","com":"10111111"}"#,
+        out
+    );
+
+    let out = run(&parse("-w 4 -f csv table and").unwrap()).unwrap();
+    assert_eq!(Some("left,right,result,N,Z,C,V"), out.lines().next());
+
+    assert_eq!(true, run(&parse("").unwrap()).is_err());
+    assert_eq!(true, run(&parse("sub 1 2 3").unwrap()).is_err());
+    assert_eq!(true, run(&parse("mul 1 2").unwrap()).is_err());
+    assert_eq!(true, run(&parse("add 256 1").unwrap()).is_err());
+    assert_eq!(
+        true,
+        run(&parse("replay /no/such/script").unwrap()).is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_memory() {
+    use ct_calculator::api::FormattedValue;
+
+    let value = ct_calculator::format(0x12345678, 32).unwrap();
+    assert_eq!(Some(vec![0x78, 0x56, 0x34, 0x12]), value.get_le_bytes());
+    assert_eq!(Some(vec![0x12, 0x34, 0x56, 0x78]), value.get_be_bytes());
+    assert_eq!(
+        Some("+0: 78  +1: 56  +2: 34  +3: 12".to_string()),
+        value.get_little_endian()
+    );
+    let value = ct_calculator::format(0x1234, 16).unwrap();
+    assert_eq!(Some("+0: 12  +1: 34".to_string()), value.get_big_endian());
+    assert_eq!(None, ct_calculator::format(0x12, 8).unwrap().get_le_bytes());
+
+    let read = |dump, little_endian, of| {
+        FormattedValue::from_memory(dump, little_endian, of).map(|value| value.get_raw())
+    };
+    assert_eq!(Ok(0x12345678), read("78 56 34 12", true, 32));
+    assert_eq!(Ok(0x78563412), read("0x78, 0x56, 0x34, 0x12", false, 32));
+    assert_eq!(Ok(0x3412), read("1234", true, 16));
+    assert_eq!(true, read("12 34 56", true, 32).is_err());
+    assert_eq!(true, read("12 zz", true, 16).is_err());
+    assert_eq!(true, read("12", true, 8).is_err());
+}
+
 fn testing_facility_results<T>(expected: &Results, left: i32, right: i32, of: i32, func: T)
 where
     T: FnOnce(i32, i32, i32) -> Result<Results, JsValue>,
@@ -27,6 +27,9 @@ var values = {
         result: null
 }
 
+// every operation shown so far, for undo, redo and export
+var past = new wasm.History()
+
 function setInput(from) {
         let value = values[from.toLowerCase()]
 
@@ -70,50 +73,18 @@ function setFlags() {
         document.getElementById('flagBorrow').innerText = borrow ? '1' : '0'
 }
 
-function setCondUnsigned() {
-        const works = (val) => val ? '1' : '0'
-        let {
-                zero,
-                carry
-        } = values.result.get_flags
-
-        document.getElementById("condUnsignedEQ").innerText = works(zero)
-        document.getElementById("condUnsignedNE").innerText = works(!zero)
-
-        document.getElementById("condUnsignedHS").innerText = works(carry)
-        document.getElementById("condUnsignedLO").innerText = works(!carry)
-
-        document.getElementById("condUnsignedHI").innerText = works(carry && !zero)
-        document.getElementById("condUnsignedLS").innerText = works(!carry || zero)
-}
-
-function setCondSigned() {
-        const works = (val) => val ? '1' : '0'
-        let {
-                zero,
-                overflow,
-                negative
-        } = values.result.get_flags
-
-        document.getElementById("condSignedEQ").innerText = works(zero)
-        document.getElementById("condSignedNE").innerText = works(!zero)
-
-        document.getElementById("condSignedMI").innerText = works(negative)
-        document.getElementById("condSignedPL").innerText = works(!negative)
-
-        document.getElementById("condSignedVS").innerText = works(overflow)
-        document.getElementById("condSignedVC").innerText = works(!overflow)
-
-        document.getElementById("condSignedGE").innerText = works(negative == overflow)
-        document.getElementById("condSignedLT").innerText = works(negative != overflow)
-
-        document.getElementById("condSignedGT").innerText = works(!zero && (negative === overflow))
-        document.getElementById("condSignedLE").innerText = works(zero || (negative !== overflow))
-}
+const UNSIGNED_CONDITIONS = ["EQ", "NE", "HS", "LO", "HI", "LS"]
+const SIGNED_CONDITIONS = ["EQ", "NE", "MI", "PL", "VS", "VC", "GE", "LT", "GT", "LE"]
 
 function setCond() {
-        setCondSigned()
-        setCondUnsigned()
+        let flags = values.result.get_flags
+
+        for (let code of UNSIGNED_CONDITIONS) {
+                document.getElementById("condUnsigned" + code).innerText = flags.condition(code) ? '1' : '0'
+        }
+        for (let code of SIGNED_CONDITIONS) {
+                document.getElementById("condSigned" + code).innerText = flags.condition(code) ? '1' : '0'
+        }
 }
 
 function calculateResult() {
@@ -164,50 +135,93 @@ function calculateResult() {
         setCond()
 }
 
-function resetField(loc) {
-        for (let [type] of inputFields) {
-                document.getElementById('input' + loc + type).value = ''
+function setHistoryButtons() {
+        document.getElementById('undo').disabled = !past.get_can_undo
+        document.getElementById('redo').disabled = !past.get_can_redo
+}
+
+function remember() {
+        if (values.left === null || values.right === null) {
+                return
         }
-        document.getElementById('input' + loc + "2C").value = ''
-        resetResults()
+
+        try {
+                past.record(operation, values.left.get_raw, values.right.get_raw, chosenBits)
+        } catch (err) {
+                console.log(err)
+                return
+        }
+        setHistoryButtons()
 }
 
-function resetCondUnsigned() {
-        const names = [
-                "condUnsignedEQ",
-                "condUnsignedNE",
-                "condUnsignedHS",
-                "condUnsignedLO",
-                "condUnsignedHI",
-                "condUnsignedLS"
-        ]
-        for (let elem of names) {
-                document.getElementById(elem).innerText = '0'
+function showEntry(entry) {
+        chosenBits = entry.get_of
+        document.getElementById(chosenBits + 'bit').checked = true
+
+        operation = entry.get_op
+        let [sign] = OPERATION.find(([, op]) => op === operation) || []
+        if (sign !== undefined) {
+                document.getElementById(sign).checked = true
         }
+
+        values.left = entry.get_left
+        values.right = entry.get_right
+        values.result = entry.get_results
+        setInput('Left')
+        setInput('Right')
+        setFlags()
+        setResult()
+        setCond()
 }
 
-function resetCondSigned() {
-        const names = [
-                "condSignedEQ",
-                "condSignedNE",
-                "condSignedMI",
-                "condSignedPL",
-                "condSignedVS",
-                "condSignedVC",
-                "condSignedGE",
-                "condSignedLT",
-                "condSignedGT",
-                "condSignedLE"
-        ]
-        for (let elem of names) {
-                document.getElementById(elem).innerText = '0'
+function showCurrent() {
+        let entry = past.get_current
+        if (entry === undefined || entry === null) {
+                values.left = null
+                values.right = null
+                reset()
+        } else {
+                showEntry(entry)
         }
+        setHistoryButtons()
+}
 
+function exportHistory() {
+        let blob = new Blob([past.script()], { type: 'text/plain' })
+        let link = document.createElement('a')
+        link.href = URL.createObjectURL(blob)
+        link.download = 'ct-calc-history.txt'
+        link.click()
+        URL.revokeObjectURL(link.href)
+}
+
+function loadHistory(file) {
+        file.text().then((script) => {
+                try {
+                        past = wasm.History.replay(script)
+                } catch (err) {
+                        alert(err)
+                        return
+                }
+                showCurrent()
+        })
+}
+
+function resetField(loc) {
+        for (let [type] of inputFields) {
+                document.getElementById('input' + loc + type).value = ''
+        }
+        document.getElementById('input' + loc + "2C").value = ''
+        resetResults()
 }
 
 function resetCond() {
-        resetCondUnsigned()
-        resetCondSigned()
+        for (let code of UNSIGNED_CONDITIONS) {
+                document.getElementById("condUnsigned" + code).innerText = '0'
+        }
+        for (let code of SIGNED_CONDITIONS) {
+                document.getElementById("condSigned" + code).innerText = '0'
+        }
 }
 
 function resetResults() {
@@ -250,12 +264,33 @@ function main() {
                 document.getElementById(sign).addEventListener('click', () => {
                         operation = op
                         calculateResult()
+                        remember()
                 })
         }
 
         // get currently choses
         operation = document.querySelector('input[name = "operation"]:checked').value
 
+        // register history listeners
+        document.getElementById('undo').addEventListener('click', () => {
+                if (past.undo()) {
+                        showCurrent()
+                }
+        })
+        document.getElementById('redo').addEventListener('click', () => {
+                if (past.redo()) {
+                        showCurrent()
+                }
+        })
+        document.getElementById('export').addEventListener('click', exportHistory)
+        document.getElementById('load').addEventListener('change', (e) => {
+                let [file] = e.currentTarget.files
+                if (file !== undefined) {
+                        loadHistory(file)
+                }
+                e.currentTarget.value = ''
+        })
+
         // register listeners on input fields
         for (let [type, base] of inputFields) {
                 for (let loc of ['Left', 'Right']) {
@@ -266,24 +301,20 @@ function main() {
                                         return
                                 }
 
-                                let ivalue = parseInt(value, base)
-
-                                if (ivalue.toString(2).length > chosenBits) {
-                                        e.currentTarget.valueOf().value = undefined
-                                        return
-                                }
-
                                 try {
-                                        let result = wasm.format(ivalue, chosenBits)
-                                        values[loc.toLowerCase()] = result
+                                        let literal = wasm.parse(value, base, chosenBits)
+                                        values[loc.toLowerCase()] = literal.get_formatted
                                 } catch (err) {
-                                        console.log("no clue what happend")
+                                        // most likely still typing, like `0x` or `-`
                                         console.log(err)
+                                        return
                                 }
 
                                 setInput(loc)
                                 calculateResult()
                         })
+                        // only finished values go into the history, not every key
+                        document.getElementById('input' + loc + type).addEventListener('change', remember)
                 }
         }
 }